@@ -12,6 +12,22 @@ fn bench_no_save(option: ParserOption, s: &str) {
   let _ = parser.feed_one('\0');
   let _ = parser;
 }
+fn bench_bulk_no_save(option: ParserOption, s: &str) {
+  let mut parser = StreamParser::new(option);
+  let mut tokens = Vec::with_capacity(4096);
+  let mut start = 0;
+  while start < s.len() {
+    let mut end = (start + 4096).min(s.len());
+    while !s.is_char_boundary(end) {
+      end += 1;
+    }
+    let _ = parser.feed_into(&s[start..end], &mut tokens);
+    tokens.clear();
+    start = end;
+  }
+  let _ = parser.feed_one('\0');
+  let _ = parser;
+}
 
 fn gen_array() -> String {
   let mut s1 = String::from("[");
@@ -22,7 +38,7 @@ fn gen_array() -> String {
   let mut s = String::from("[");
   s.push_str(&s1.repeat(1000));
   s.pop();
-  s.push_str("]");
+  s.push(']');
 
   s
 }
@@ -37,28 +53,28 @@ fn gen_object() -> String {
     ));
   }
   s1.pop();
-  s1.push_str("}");
+  s1.push('}');
 
   let mut s = String::from("{");
   for i in 0..2000 {
     s.push_str(&format!(r#""{}":{},"#, table[i % table.len()] as char, s1));
   }
   s.pop();
-  s.push_str("}");
+  s.push('}');
 
   s
 }
 fn gen_number() -> String {
   let mut s = String::from("-1");
-  for i in 1..(1000000 as usize) {
+  for i in 1..1000000 {
     s.push(((i % 10) as u8 + b'1') as char);
   }
   s.push('.');
-  for i in 0..(1000000 as usize) {
+  for i in 0..1000000 {
     s.push(((i % 10) as u8 + b'1') as char);
   }
   s.push('e');
-  for i in 0..(1000000 as usize) {
+  for i in 0..1000000 {
     s.push(((i % 10) as u8 + b'1') as char);
   }
 
@@ -156,9 +172,59 @@ fn bench_stream_no_save(c: &mut Criterion) {
   }
 }
 
+/* without the options of `ParserOption::make_repair`, which feed characters one by one */
+fn bench_stream_bulk_no_save(c: &mut Criterion) {
+  let option = ParserOption::make_json5();
+  {
+    let s = gen_array();
+    c.bench_function("[bulk_no_save]array", |b| {
+      b.iter(|| bench_bulk_no_save(option, &s));
+    });
+    c.bench_function("[bulk_baseline_no_save]array", |b| {
+      b.iter(|| bench_no_save(option, &s));
+    });
+  }
+  {
+    let s = gen_object();
+    c.bench_function("[bulk_no_save]object", |b| {
+      b.iter(|| bench_bulk_no_save(option, &s));
+    });
+    c.bench_function("[bulk_baseline_no_save]object", |b| {
+      b.iter(|| bench_no_save(option, &s));
+    });
+  }
+  {
+    let s = gen_number();
+    c.bench_function("[bulk_no_save]number", |b| {
+      b.iter(|| bench_bulk_no_save(option, &s));
+    });
+    c.bench_function("[bulk_baseline_no_save]number", |b| {
+      b.iter(|| bench_no_save(option, &s));
+    });
+  }
+  {
+    let s = gen_string();
+    c.bench_function("[bulk_no_save]string", |b| {
+      b.iter(|| bench_bulk_no_save(option, &s));
+    });
+    c.bench_function("[bulk_baseline_no_save]string", |b| {
+      b.iter(|| bench_no_save(option, &s));
+    });
+  }
+  {
+    let s = gen_recursive_array();
+    c.bench_function("[bulk_no_save]recursive_array", |b| {
+      b.iter(|| bench_bulk_no_save(option, &s));
+    });
+    c.bench_function("[bulk_baseline_no_save]recursive_array", |b| {
+      b.iter(|| bench_no_save(option, &s));
+    });
+  }
+}
+
 criterion_group! {
   name=benches;
   config=Criterion::default().sample_size(10).measurement_time(Duration::from_secs(10));
-  targets=bench_stream,bench_stream_no_save
+  targets=bench_stream,bench_stream_no_save,bench_stream_bulk_no_save
 }
 criterion_main!(benches);
@@ -1,3 +1,32 @@
+/** The default size (in bytes) of the stack with the feature `fixed-stack` */
+const DEFAULT_FIXED_STACK: usize = 64;
+
 pub fn main() {
-  cc::Build::new().file("c_src/build.c").compile("efjsonc");
+  println!("cargo:rerun-if-changed=c_src");
+  println!("cargo:rerun-if-env-changed=EFJSON_FIXED_STACK");
+  let feature = |name: &str| std::env::var_os(format!("CARGO_FEATURE_{}", name)).is_some();
+  let flag = |enabled: bool| if enabled { "1" } else { "0" };
+
+  let fixed_stack = if feature("FIXED_STACK") {
+    match std::env::var("EFJSON_FIXED_STACK") {
+      Ok(size) => match size.parse::<usize>() {
+        Ok(size) if size > 0 => size,
+        _ => panic!("EFJSON_FIXED_STACK must be a positive integer, but got {:?}", size),
+      },
+      Err(_) => DEFAULT_FIXED_STACK,
+    }
+  } else {
+    0
+  };
+  /* the Rust side needs the size for the layout of `efjsonStreamParser` */
+  println!("cargo:rustc-env=EFJSON_FIXED_STACK={}", fixed_stack);
+
+  cc::Build::new()
+    .file("c_src/build.c")
+    .define("EFJSON_CONF_FIXED_STACK", fixed_stack.to_string().as_str())
+    .define("EFJSON_CONF_UNICODE", flag(!feature("ASCII_ONLY")))
+    .define("EFJSON_CONF_COMBINE_ESCAPED_SURROGATE", flag(!feature("NO_COMBINE_SURROGATE")))
+    .define("EFJSON_CONF_CHECK_INPUT_UTF", flag(feature("CHECK_INPUT_UTF")))
+    .define("EFJSON_CONF_CHECK_POSITION_OVERFLOW", flag(feature("CHECK_POSITION_OVERFLOW")))
+    .compile("efjsonc");
 }
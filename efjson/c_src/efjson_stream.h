@@ -424,6 +424,12 @@ enum efjsonTokenType /* : efjsonUint16 */ {
   efjsonType_STRING_NEXT_LINE = efjsonCategory_STRING << efjson_TOKEN_CATEGORY_SHIFT | 0x7,
   efjsonType_STRING_ESCAPE_HEX_START = efjsonCategory_STRING << efjson_TOKEN_CATEGORY_SHIFT | 0x8,
   efjsonType_STRING_ESCAPE_HEX = efjsonCategory_STRING << efjson_TOKEN_CATEGORY_SHIFT | 0x9,
+  /**
+   * a lone surrogate with `efjsonOption_LONE_SURROGATE`, reported at the last hexadecimal digit (`index` 3, `done` 1),
+   * or for a high surrogate, at the character breaking the pair (`index` 0, `done` 0),
+   * which is not consumed and must be fed again
+   */
+  efjsonType_STRING_ESCAPE_SURROGATE = efjsonCategory_STRING << efjson_TOKEN_CATEGORY_SHIFT | 0xA,
 #endif
 
   efjsonType_NUMBER_INTEGER_DIGIT = efjsonCategory_NUMBER << efjson_TOKEN_CATEGORY_SHIFT | 0x0,
@@ -562,6 +568,7 @@ typedef struct efjsonToken {
    * | `efjsonType_STRING_ESCAPE`              | 0       | 1      | escape  |
    * | `efjsonType_STRING_ESCAPE_UNICODE` [^1] | 0..=3   | 0,1    | escape  |
    * | `efjsonType_STRING_ESCAPE_HEX`          | 0,1     | 0,1    | escape  |
+   * | `efjsonType_STRING_ESCAPE_SURROGATE`    | 0,3     | 0,1    | escape  |
    * | `efjsonType_IDENTIFIER_ESCAPE_START`    | 0,1     | 0,1    | 0       |
    * | `efjsonType_IDENTIFIER_ESCAPE` [^2]     | 0..=3   | 0,1    | escape  |
    *
@@ -685,6 +692,26 @@ EFJSON_PUBLIC efjsonUint8 efjson_getError(efjsonToken token);
    * whether to allow empty json value
    */
   #define efjsonOption_ALLOW_EMPTY_VALUE 0x020000u
+  /**
+   * whether to accept unpaired surrogates in escapes (each of them is reported as `efjsonType_STRING_ESCAPE_SURROGATE`,
+   * escaped surrogate pairs are still combined if `EFJSON_CONF_COMBINE_ESCAPED_SURROGATE` is set)
+   * @example '"\\uD800"', '"\\uDE00\\uD83D"'
+   */
+  #define efjsonOption_LONE_SURROGATE 0x040000u
+
+  /* << repair >> */
+  /**
+   * whether to accept the literals of Python (`True`, `False`, and `None` unless `efjsonOption_NAN` is set)
+   */
+  #define efjsonOption_PYTHON_LITERAL 0x100000u
+  /**
+   * whether to accept unescaped line feeds and carriage returns in string
+   */
+  #define efjsonOption_UNESCAPED_NEWLINE 0x200000u
+  /**
+   * whether to ignore the text after the root value (reported as whitespace)
+   */
+  #define efjsonOption_TRAILING_TEXT 0x400000u
 
   #define EFJSON_JSONC_OPTION \
     efjson_cast(efjsonUint32, efjsonOption_SINGLE_LINE_COMMENT | efjsonOption_MULTI_LINE_COMMENT)
@@ -734,7 +761,10 @@ EFJSON_PUBLIC efjsonToken efjsonStreamParser_feedOne(efjsonStreamParser* parser,
 /**
  * Pass multiple UTF-32 codepoints to the parser.
  * @note If the string ends, remember to pass `EOF` to parser.
- * @return 0 if failed (and error will be writen to `dest[0]`), or the number of tokens if success.
+ * @note With `efjsonOption_LONE_SURROGATE`, it also stops at a character which is not consumed
+ *       (see `efjsonType_STRING_ESCAPE_SURROGATE`), then its token is writen to `dest[i]` and `i` is returned,
+ *       where `i` is the number of characters consumed, the character must be passed again.
+ * @return 0 if failed (and error will be writen to `dest[0]`, whose type is 0), or the number of tokens if success.
  */
 EFJSON_PUBLIC size_t
 efjsonStreamParser_feed(efjsonStreamParser* parser, efjsonToken* dest, const efjsonUint32* src, size_t len);
@@ -799,7 +829,8 @@ EFJSON_PRIVATE const char* const efjson__TYPE_FORMAT[] = {
 [string]escape_unicode_start\0[string]escape_unicode\0"
     #if EFJSON_CONF_EXTENDED_JSON
   "[string]next_line\0\
-[string]escape_hex_start\0[string]escape_hex\0"
+[string]escape_hex_start\0[string]escape_hex\0\
+[string]escape_surrogate\0"
     #endif /* EFJSON_CONF_EXTENDED_JSON */
   ,
   "[number]integer_digit\0[number]fraction_digit\0[number]exponent_digit\0\
@@ -816,7 +847,7 @@ EFJSON_PRIVATE const char* const efjson__TYPE_FORMAT[] = {
   "[array]start\0[array]next\0[array]end\0",
     #if EFJSON_CONF_EXTENDED_JSON
   "[identifier]normal\0[identifier]escape_start\0[identifier]escape\0",
-  "[comment]may_start\0[comment]single_line\0[comment]multi_line\0[comment]multi_line_end\0",
+  "[comment]may_start\0[comment]single_line\0<unknown>\0[comment]multi_line\0[comment]multi_line_end\0",
     #endif /* EFJSON_CONF_EXTENDED_JSON */
 };
 EFJSON_PUBLIC const char* efjson_stringifyType(efjsonUint16 type) {
@@ -1547,8 +1578,10 @@ EFJSON_PUBLIC int efjsonUtf16Decoder_feed(efjsonUtf16Decoder* decoder, efjsonUin
   if(decoder->first != 0) {
     if(ul_likely(0xDC00u <= c && c <= 0xDFFFu)) {
       *result = (efjson_cast(efjsonUint32, decoder->first & 0x3FFu) << 10 | (c & 0x3FFu)) + 0x10000u;
+      decoder->first = 0;
       return 1;
     }
+    decoder->first = 0;
     return -1;
   }
   if(0xD800u <= c && c <= 0xDBFFu) {
@@ -1649,11 +1682,18 @@ EFJSON_PRIVATE const efjsonUint8 efjson__LOCATION_TABLE[] = {
 
 enum {
   efjsonFlag__MeetCr = 0x1,
-  efjsonFlag__SingleQuote = 0x2
+  efjsonFlag__SingleQuote = 0x2,
+  /* the literal `None` of `efjsonOption_PYTHON_LITERAL` is being parsed */
+  efjsonFlag__PythonNone = 0x4,
+  /* the text after the root value is being ignored (`efjsonOption_TRAILING_TEXT`) */
+  efjsonFlag__TrailingText = 0x8
 };
 
 
 EFJSON_PRIVATE const efjsonUint8 efjson__LITERAL_NULL[] = { 0x6E, 0x75, 0x6C, 0x6C } /* "null" */;
+  #if EFJSON_CONF_EXTENDED_JSON
+EFJSON_PRIVATE const efjsonUint8 efjson__LITERAL_NONE[] = { 0x4E, 0x6F, 0x6E, 0x65 } /* "None" */;
+  #endif /* EFJSON_CONF_EXTENDED_JSON */
 EFJSON_PRIVATE const efjsonUint8 efjson__LITERAL_TRUE[] = { 0x74, 0x72, 0x75, 0x65 } /* "true" */;
 EFJSON_PRIVATE const efjsonUint8 efjson__LITERAL_FALSE[] = { 0x66, 0x61, 0x6C, 0x73, 0x65 } /* "false" */;
 EFJSON_PRIVATE const efjsonUint8 efjson__LITERAL_INFINITY[] = {
@@ -1868,6 +1908,12 @@ EFJSON_PRIVATE void efjsonStreamParser__handleNumberSeparator(
   }
 }
 EFJSON_PRIVATE void efjsonStreamParser__handleEmpty(efjsonStreamParser* parser, efjsonUint32 u, efjsonToken* token) {
+  #if EFJSON_CONF_EXTENDED_JSON
+  if(ul_unlikely(parser->flag & efjsonFlag__TrailingText) && u != 0x00) {
+    token->type = efjsonType_WHITESPACE;
+    return;
+  }
+  #endif /* EFJSON_CONF_EXTENDED_JSON */
   if(
   #if EFJSON_CONF_EXTENDED_JSON
     efjson_isWhitespace(u, (parser->option & efjsonOption_JSON5_WHITESPACE) != 0)
@@ -1878,7 +1924,13 @@ EFJSON_PRIVATE void efjsonStreamParser__handleEmpty(efjsonStreamParser* parser,
     token->type = efjsonType_WHITESPACE;
   } else if(ul_unlikely(u == 0x00)) {
     efjsonStreamParser__handleEof(parser, token);
-  } else if(ul_unlikely(u == 0x2F /* '/' */)) {
+  } else if(ul_unlikely(u == 0x2F /* '/' */)
+            && (parser->location != efjsonLoc__ROOT_END
+  #if EFJSON_CONF_EXTENDED_JSON
+                || !(parser->option & efjsonOption_TRAILING_TEXT)
+                || (parser->option & (efjsonOption_SINGLE_LINE_COMMENT | efjsonOption_MULTI_LINE_COMMENT))
+  #endif /* EFJSON_CONF_EXTENDED_JSON */
+                )) {
   #if EFJSON_CONF_EXTENDED_JSON
     if(parser->option & (efjsonOption_SINGLE_LINE_COMMENT | efjsonOption_MULTI_LINE_COMMENT)) {
       parser->state = efjsonVal__COMMENT_MAY_START;
@@ -1887,7 +1939,13 @@ EFJSON_PRIVATE void efjsonStreamParser__handleEmpty(efjsonStreamParser* parser,
   #endif /* EFJSON_CONF_EXTENDED_JSON */
       token->extra = efjsonError_COMMENT_FORBIDDEN;
   } else if(ul_unlikely(parser->location == efjsonLoc__ROOT_END)) {
-    token->extra = efjsonError_NONWHITESPACE_AFTER_END;
+  #if EFJSON_CONF_EXTENDED_JSON
+    if(parser->option & efjsonOption_TRAILING_TEXT) {
+      parser->flag |= efjsonFlag__TrailingText;
+      token->type = efjsonType_WHITESPACE;
+    } else
+  #endif /* EFJSON_CONF_EXTENDED_JSON */
+      token->extra = efjsonError_NONWHITESPACE_AFTER_END;
   } else {
     switch(parser->location) {
     case efjsonLoc__KEY_FIRST_START:
@@ -2108,6 +2166,11 @@ EFJSON_PRIVATE void efjsonStreamParser__handleEmpty(efjsonStreamParser* parser,
           parser->state = efjsonVal__NUMBER_NAN;
           parser->substate = 1;
           token->type = efjsonType_NUMBER_NAN;
+        } else if(parser->option & efjsonOption_PYTHON_LITERAL) {
+          parser->state = efjsonVal__NULL;
+          parser->substate = 1;
+          parser->flag |= efjsonFlag__PythonNone;
+          token->type = efjsonType_NULL;
         } else
   #endif /* EFJSON_CONF_EXTENDED_JSON */
           token->extra = efjsonError_UNEXPECTED_IN_NUMBER;
@@ -2126,6 +2189,7 @@ EFJSON_PRIVATE void efjsonStreamParser__handleEmpty(efjsonStreamParser* parser,
       case 0x6E /* 'n' */:
         parser->state = efjsonVal__NULL;
         parser->substate = 1;
+        parser->flag &= ~efjsonFlag__PythonNone;
         token->type = efjsonType_NULL;
         break;
       case 0x74 /* 't' */:
@@ -2138,6 +2202,16 @@ EFJSON_PRIVATE void efjsonStreamParser__handleEmpty(efjsonStreamParser* parser,
         parser->substate = 1;
         token->type = efjsonType_FALSE;
         break;
+  #if EFJSON_CONF_EXTENDED_JSON
+      case 0x54 /* 'T' */:
+      case 0x46 /* 'F' */:
+        if(parser->option & efjsonOption_PYTHON_LITERAL) {
+          parser->state = u == 0x54 ? efjsonVal__TRUE : efjsonVal__FALSE;
+          parser->substate = 1;
+          token->type = u == 0x54 ? efjsonType_TRUE : efjsonType_FALSE;
+        } else token->extra = efjsonError_UNEXPECTED;
+        break;
+  #endif /* EFJSON_CONF_EXTENDED_JSON */
 
       default:
         if(ul_unlikely(u == 0x3A /* ':' */)) {
@@ -2153,6 +2227,22 @@ EFJSON_PRIVATE void efjsonStreamParser__handleEmpty(efjsonStreamParser* parser,
     }
   }
 }
+  #if EFJSON_CONF_COMBINE_ESCAPED_SURROGATE && EFJSON_CONF_EXTENDED_JSON
+/**
+ * Report the high surrogate as lone when the character breaks the pair (with `efjsonOption_LONE_SURROGATE`),
+ * the character is not consumed and will be fed again in `state`.
+ * @return whether the high surrogate is reported
+ */
+EFJSON_PRIVATE int efjsonStreamParser__breakPair(efjsonStreamParser* parser, efjsonToken* token, efjsonUint8 state) {
+  if(!(parser->option & efjsonOption_LONE_SURROGATE)) return 0;
+  parser->state = state;
+  token->type = efjsonType_STRING_ESCAPE_SURROGATE;
+  token->index = 0;
+  token->done = 0;
+  token->extra = parser->prevPair;
+  return 1;
+}
+  #endif
 EFJSON_PRIVATE efjsonToken efjsonStreamParser__step(efjsonStreamParser* parser, efjsonUint32 u) {
   efjsonToken token = { /* .type = */ efjsonType_ERROR,
                         /* .dummy_ = */ 0,
@@ -2168,10 +2258,19 @@ EFJSON_PRIVATE efjsonToken efjsonStreamParser__step(efjsonStreamParser* parser,
     efjsonStreamParser__handleEmpty(parser, u, &token);
     break;
   case efjsonVal__NULL:
+  #if EFJSON_CONF_EXTENDED_JSON
+    if(ul_likely(
+         u
+         == (ul_unlikely(parser->flag & efjsonFlag__PythonNone) ? efjson__LITERAL_NONE
+                                                                  : efjson__LITERAL_NULL)[parser->substate]
+       )) {
+  #else  /* !EFJSON_CONF_EXTENDED_JSON */
     if(ul_likely(u == efjson__LITERAL_NULL[parser->substate])) {
+  #endif /* EFJSON_CONF_EXTENDED_JSON */
       token.type = efjsonType_NULL;
       token.index = parser->substate;
       if((token.done = (++parser->substate == 4))) {
+        parser->flag &= ~efjsonFlag__PythonNone;
         parser->state = efjsonVal__EMPTY;
         parser->location = efjson__nextLocation(parser->location);
       }
@@ -2238,6 +2337,10 @@ EFJSON_PRIVATE efjsonToken efjsonStreamParser__step(efjsonStreamParser* parser,
       parser->state = efjsonVal__STRING_ESCAPE;
       token.type = efjsonType_STRING_ESCAPE_START;
     } else if(ul_unlikely(u == 0x00)) token.extra = efjsonError_EOF;
+  #if EFJSON_CONF_EXTENDED_JSON
+    else if(ul_unlikely(u == 0x0A /* '\n' */ || u == 0x0D /* '\r' */) && (parser->option & efjsonOption_UNESCAPED_NEWLINE))
+      token.type = efjsonType_STRING_NORMAL;
+  #endif /* EFJSON_CONF_EXTENDED_JSON */
     else if(ul_unlikely(efjson__isControl(u))) token.extra = efjsonError_CONTROL_CHARACTER_FORBIDDEN_IN_STRING;
   #if EFJSON_CONF_CHECK_INPUT_UTF
     else if(ul_unlikely(efjson__isUtf16Surrogate(u) || u > 0x10FFFFu)) token.extra = efjsonError_INVALID_INPUT_UTF;
@@ -2324,6 +2427,20 @@ EFJSON_PRIVATE efjsonToken efjsonStreamParser__step(efjsonStreamParser* parser,
       token.type = efjsonType_STRING_ESCAPE_UNICODE;
       token.index = parser->substate;
       if((token.done = (++parser->substate == 4))) {
+  #if EFJSON_CONF_EXTENDED_JSON
+    #if EFJSON_CONF_COMBINE_ESCAPED_SURROGATE
+        /* a high surrogate is lone only if the next escape is not a low one */
+        if(ul_unlikely(parser->escape >= 0xDC00u && parser->escape <= 0xDFFFu)
+           && (parser->option & efjsonOption_LONE_SURROGATE)) {
+    #else
+        if(ul_unlikely(efjson__isUtf16Surrogate(parser->escape)) && (parser->option & efjsonOption_LONE_SURROGATE)) {
+    #endif
+          parser->state = efjsonVal__STRING;
+          token.type = efjsonType_STRING_ESCAPE_SURROGATE;
+          token.extra = efjson_cast(efjsonUint16, parser->escape);
+          return token;
+        }
+  #endif
   #if EFJSON_CONF_COMBINE_ESCAPED_SURROGATE
         if(ul_unlikely(parser->escape >= 0xD800u && parser->escape <= 0xDBFFu)) {
           token.done = 0;
@@ -2357,7 +2474,11 @@ EFJSON_PRIVATE efjsonToken efjsonStreamParser__step(efjsonStreamParser* parser,
         parser->substate = 1;
         token.index = 4;
         token.type = efjsonType_STRING_ESCAPE_UNICODE;
-      } else token.extra = efjsonError_BAD_UNICODE_ESCAPE_IN_STRING;
+      } else
+    #if EFJSON_CONF_EXTENDED_JSON
+        if(!efjsonStreamParser__breakPair(parser, &token, efjsonVal__STRING))
+    #endif
+          token.extra = efjsonError_BAD_UNICODE_ESCAPE_IN_STRING;
       break;
     case 1:
       if(ul_likely(u == 0x75 /* 'u' */)) {
@@ -2365,7 +2486,11 @@ EFJSON_PRIVATE efjsonToken efjsonStreamParser__step(efjsonStreamParser* parser,
         token.index = 5;
         token.type = efjsonType_STRING_ESCAPE_UNICODE;
         parser->escape = 0;
-      } else token.extra = efjsonError_BAD_UNICODE_ESCAPE_IN_STRING;
+      } else
+    #if EFJSON_CONF_EXTENDED_JSON
+        if(!efjsonStreamParser__breakPair(parser, &token, efjsonVal__STRING_ESCAPE))
+    #endif
+          token.extra = efjsonError_BAD_UNICODE_ESCAPE_IN_STRING;
       break;
     default:
       if(ul_likely(efjson__isHexDigit(u))) {
@@ -2381,6 +2506,14 @@ EFJSON_PRIVATE efjsonToken efjsonStreamParser__step(efjsonStreamParser* parser,
           } else {
             --parser->substate;
             parser->escape >>= 4;
+    #if EFJSON_CONF_EXTENDED_JSON
+            /* the last digit is fed again as the fourth one of a single escape */
+            if(efjsonStreamParser__breakPair(parser, &token, efjsonVal__STRING_UNICODE)) {
+              parser->substate = 3;
+              break;
+            }
+    #endif
+            token.type = efjsonType_ERROR;
             token.done = 0;
             token.index = 0;
             token.extra = efjsonError_INCOMPLETE_SURROGATE_PAIR;
@@ -2668,6 +2801,7 @@ EFJSON_PRIVATE efjsonToken efjsonStreamParser__step(efjsonStreamParser* parser,
           }
     #endif
           parser->state = efjsonVal__IDENTIFIER;
+          token.extra = parser->escape;
         }
       } else token.extra = efjsonError_INVALID_IDENTIFIER_ESCAPE;
     }
@@ -2684,9 +2818,10 @@ EFJSON_PRIVATE efjsonToken efjsonStreamParser__step(efjsonStreamParser* parser,
       break;
     case 1:
       if(ul_likely(u == 0x75 /* 'u' */)) {
-        parser->substate = 1;
-        token.index = 4;
+        parser->substate = 2;
+        token.index = 5;
         token.type = efjsonType_IDENTIFIER_ESCAPE;
+        parser->escape = 0;
       } else token.extra = efjsonError_BAD_IDENTIFIER_ESCAPE;
       break;
     default:
@@ -2703,12 +2838,13 @@ EFJSON_PRIVATE efjsonToken efjsonStreamParser__step(efjsonStreamParser* parser,
           } else {
             --parser->substate;
             parser->escape >>= 4;
+            token.type = efjsonType_ERROR;
             token.done = 0;
             token.index = 0;
             token.extra = efjsonError_INCOMPLETE_SURROGATE_PAIR;
           }
         }
-      } else token.extra = efjsonError_BAD_UNICODE_ESCAPE_IN_STRING;
+      } else token.extra = efjsonError_INVALID_IDENTIFIER_ESCAPE;
     }
     break;
     #endif /* EFJSON_CONF_COMBINE_ESCAPED_SURROGATE */
@@ -2775,8 +2911,12 @@ EFJSON_PUBLIC int(efjsonStreamParser_initCopy)(efjsonStreamParser* parser, const
   #if EFJSON_CONF_FIXED_STACK > 0
     memcpy(parser, src, sizeof(efjsonStreamParser));
   #else
-    efjsonUint8* stack = (efjsonUint8*)malloc(efjson_cast(efjsonStackLength, parser->cap));
-    if(ul_unlikely(!stack)) return -1;
+    efjsonUint8* stack = NULL;
+    if(src->cap != 0) {
+      stack = efjson_reptr(efjsonUint8*, malloc(efjson_cast(size_t, src->cap)));
+      if(ul_unlikely(!stack)) return -1;
+      memcpy(stack, src->stack, efjson_cast(size_t, src->cap));
+    }
     memcpy(parser, src, sizeof(efjsonStreamParser));
     parser->stack = stack;
   #endif
@@ -2788,7 +2928,7 @@ EFJSON_PUBLIC void(efjsonStreamParser_initMove)(efjsonStreamParser* parser, efjs
     memcpy(parser, src, sizeof(efjsonStreamParser));
   #if !(EFJSON_CONF_FIXED_STACK > 0)
     src->stack = NULL;
-    parser->len = 0;
+    src->len = 0;
     src->cap = 0;
   #endif
   }
@@ -2821,6 +2961,12 @@ EFJSON_PUBLIC efjsonStreamParser* efjsonStreamParser_newCopy(const efjsonStreamP
   #else
     #define efjsonStreamParser__checkPosition(parser, uc, token, fail_stat)
   #endif
+  #if EFJSON_CONF_COMBINE_ESCAPED_SURROGATE && EFJSON_CONF_EXTENDED_JSON
+    #define efjsonStreamParser__isConsumed(token) \
+      ul_likely((token).type != efjsonType_STRING_ESCAPE_SURROGATE || (token).done)
+  #else
+    #define efjsonStreamParser__isConsumed(token) 1
+  #endif
   #define efjsonStreamParser__movePosition(parser, uc)     \
     if(ul_unlikely((parser)->flag & efjsonFlag__MeetCr)) { \
       if(ul_unlikely((uc) != 0x0A /* '\n' */)) {           \
@@ -2846,7 +2992,7 @@ EFJSON_PUBLIC efjsonToken efjsonStreamParser_feedOne(efjsonStreamParser* parser,
   efjsonToken token;
   efjsonStreamParser__checkPosition(parser, u, token, return token;);
   token = efjsonStreamParser__step(parser, u);
-  if(ul_likely(token.type != 0)) {
+  if(ul_likely(token.type != 0) && efjsonStreamParser__isConsumed(token)) {
     efjsonStreamParser__movePosition(parser, u);
   }
   return token;
@@ -2857,8 +3003,10 @@ efjsonStreamParser_feed(efjsonStreamParser* parser, efjsonToken* dest, const efj
   for(i = 0; i < len; ++i) {
     efjsonStreamParser__checkPosition(parser, src[i], dest[0], return 0;);
     dest[i] = efjsonStreamParser__step(parser, src[i]);
-    if(ul_likely(dest[i].type != 0)) {
+    if(ul_likely(dest[i].type != 0) && efjsonStreamParser__isConsumed(dest[i])) {
       efjsonStreamParser__movePosition(parser, src[i]);
+    } else if(dest[i].type != 0) {
+      return i;
     } else {
       dest[0] = dest[i];
       return 0;
@@ -2867,6 +3015,7 @@ efjsonStreamParser_feed(efjsonStreamParser* parser, efjsonToken* dest, const efj
   return i;
 }
   #undef efjsonStreamParser__checkPosition
+  #undef efjsonStreamParser__isConsumed
   #undef efjsonStreamParser__movePosition
 
 
@@ -2883,7 +3032,7 @@ EFJSON_PUBLIC efjsonUint8 efjsonStreamParser_getLocation(const efjsonStreamParse
   return efjson__transformLocation(parser->location);
 }
 EFJSON_PUBLIC enum efjsonStage efjsonStreamParser_getStage(const efjsonStreamParser* parser) {
-  if(parser->state == efjsonVal__EMPTY) return efjsonStage_PARSING;
+  if(parser->state != efjsonVal__EMPTY) return efjsonStage_PARSING;
   else if(parser->location == efjsonLoc__ROOT_START) return efjsonStage_NOT_STARTED;
   else if(parser->location == efjsonLoc__ROOT_END || parser->location == efjsonLoc__EOF) return efjsonStage_ENDED;
   else return efjsonStage_PARSING;
@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 use efjson::{deserialize::deserialize, Deserializable, ParserOption};
 
-const SRC: &'static str = r#"{
+const SRC: &str = r#"{
 "n":null,"t":true,"f":false,
 "str":"str,\"esc\",\uD83D\uDE00,😊",
 "num":-1.2e3,"arr":["A",{"obj":"B"}]
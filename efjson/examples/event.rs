@@ -3,7 +3,7 @@ use efjson::{
   ParserOption,
 };
 
-const SRC: &'static str = r#"{
+const SRC: &str = r#"{
 "N":null,"T":true,"F":false,
 "str":"str,\"esc\",\uD83D\uDE00,😊",
 "num":-1.2e3,"arr":["A",{"obj":"B"}]
@@ -44,6 +44,58 @@ bitflags::bitflags! {
     /* << other >> */
     /** whether to allow empty json value */
     const ALLOW_EMPTY_VALUE        = 0x020000;
+    /**
+    whether to accept unpaired surrogates in string escapes (for example, `"\\uD800"`),
+    each of them is then reported as `TokenInfo::StringEscapeSurrogate`,
+    while escaped surrogate pairs are still combined (unless the `no-combine-surrogate` feature is enabled)
+    */
+    const LONE_SURROGATE           = 0x040000;
+    /* << repair >> */
+    /**
+    whether to insert a missing comma between elements or members (for example, `[1 2]`),
+    see `StreamParser::get_warnings` for the mistakes repaired
+    */
+    const MISSING_COMMA            = 0x080000;
+    /**
+    whether to accept `True`, `False` and `None` (`None` is not accepted with `NAN`)
+
+    # Example
+    ```rust
+    use efjson::{stream_parser::{StreamParser, TokenInfo}, ParserOption};
+    let tokens = StreamParser::parse(ParserOption::PYTHON_LITERAL, "[True,None]").unwrap();
+    assert_eq!(tokens[1].info, TokenInfo::True(0, false));
+    assert_eq!(tokens[9].info, TokenInfo::Null(3, true));
+    assert!(StreamParser::parse(ParserOption::empty(), "True").is_err());
+    ```
+    */
+    const PYTHON_LITERAL           = 0x100000;
+    /**
+    whether to accept unescaped line breaks in string
+
+    # Example
+    ```rust
+    use efjson::{stream_parser::{StreamParser, TokenInfo}, ParserOption};
+    let tokens = StreamParser::parse(ParserOption::UNESCAPED_NEWLINE, "\"a\r\nb\"").unwrap();
+    assert_eq!(tokens[2].info, TokenInfo::StringNormal);
+    assert_eq!(tokens[3].info, TokenInfo::StringNormal);
+    assert!(StreamParser::parse(ParserOption::empty(), "\"a\nb\"").is_err());
+    ```
+    */
+    const UNESCAPED_NEWLINE        = 0x200000;
+    /**
+    whether to ignore the text after the root value (for example, `{} and more`),
+    the text is reported as whitespace
+
+    # Example
+    ```rust
+    use efjson::{stream_parser::{StreamParser, TokenInfo}, ParserOption};
+    let tokens = StreamParser::parse(ParserOption::TRAILING_TEXT, "[] // \"{").unwrap();
+    assert!(tokens[2..8].iter().all(|token| token.info == TokenInfo::Whitespace));
+    assert_eq!(tokens[8].info, TokenInfo::Eof);
+    assert!(StreamParser::parse(ParserOption::empty(), "[] x").is_err());
+    ```
+    */
+    const TRAILING_TEXT            = 0x400000;
   }
 }
 impl Default for ParserOption {
@@ -72,6 +124,16 @@ impl ParserOption {
       | Self::INFINITY
       | Self::HEXADECIMAL_INTEGER
   }
+  /** The options repairing the common mistakes of JSON written by hand or generated by tools */
+  pub fn make_repair() -> Self {
+    Self::TRAILING_COMMA_IN_ARRAY
+      | Self::TRAILING_COMMA_IN_OBJECT
+      | Self::SINGLE_QUOTE
+      | Self::MISSING_COMMA
+      | Self::PYTHON_LITERAL
+      | Self::UNESCAPED_NEWLINE
+      | Self::TRAILING_TEXT
+  }
 }
 
 #[derive(Clone, Debug)]
@@ -2,7 +2,7 @@ use std::{rc::Rc, sync::Arc};
 
 use crate::{
   deserialize::{DefaultDeserializable, DeserError, DeserResult, Deserializer},
-  stream_parser::Token,
+  stream_parser::{CoarseToken, Token},
 };
 
 pub struct BoxDeserializer<T>
@@ -18,6 +18,9 @@ where
   fn feed_token(&mut self, token: Token) -> Result<DeserResult<Box<T>>, DeserError> {
     Ok(self.deserializer.feed_token(token)?.map(|v| Box::new(v)))
   }
+  fn feed_coarse(&mut self, token: CoarseToken) -> Result<DeserResult<Box<T>>, DeserError> {
+    Ok(self.deserializer.feed_coarse(token)?.map(|v| Box::new(v)))
+  }
 }
 impl<T> DefaultDeserializable<Box<T>> for Box<T>
 where
@@ -42,6 +45,9 @@ where
   fn feed_token(&mut self, token: Token) -> Result<DeserResult<Rc<T>>, DeserError> {
     Ok(self.deserializer.feed_token(token)?.map(|v| Rc::new(v)))
   }
+  fn feed_coarse(&mut self, token: CoarseToken) -> Result<DeserResult<Rc<T>>, DeserError> {
+    Ok(self.deserializer.feed_coarse(token)?.map(|v| Rc::new(v)))
+  }
 }
 impl<T> DefaultDeserializable<Rc<T>> for Rc<T>
 where
@@ -66,6 +72,9 @@ where
   fn feed_token(&mut self, token: Token) -> Result<DeserResult<Arc<T>>, DeserError> {
     Ok(self.deserializer.feed_token(token)?.map(|v| Arc::new(v)))
   }
+  fn feed_coarse(&mut self, token: CoarseToken) -> Result<DeserResult<Arc<T>>, DeserError> {
+    Ok(self.deserializer.feed_coarse(token)?.map(|v| Arc::new(v)))
+  }
 }
 impl<T> DefaultDeserializable<Arc<T>> for Arc<T>
 where
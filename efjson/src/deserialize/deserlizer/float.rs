@@ -1,6 +1,6 @@
 use crate::{
-  deserialize::{DefaultDeserializable, DeserError, DeserResult, Deserializer},
-  stream_parser::TokenInfo,
+  deserialize::{feed_split, DefaultDeserializable, DeserError, DeserResult, Deserializer},
+  stream_parser::{CoarseToken, TokenInfo},
 };
 
 fn to_hexdigit(c: u8) -> u8 {
@@ -109,6 +109,21 @@ macro_rules! float_deserializer {
           }
         }
       }
+      fn feed_coarse(&mut self, token: CoarseToken) -> Result<DeserResult<$typ>, DeserError> {
+        match token {
+          /* every character of a number is pushed, except the sign of the integer */
+          CoarseToken::Number(text, TokenInfo::NumberIntegerSign) => {
+            self.is_neg = text.starts_with('-');
+            self.list.push_str(&text[1..]);
+            Ok(DeserResult::Continue)
+          }
+          CoarseToken::Number(text, _) => {
+            self.list.push_str(text);
+            Ok(DeserResult::Continue)
+          }
+          token => feed_split(token, |token| Deserializer::<$typ>::feed_token(self, token)),
+        }
+      }
     }
     impl DefaultDeserializable<$typ> for $typ {
       type DefaultDeserializer = FloatDeserializer;
@@ -1,8 +1,26 @@
 use crate::{
-  deserialize::{DefaultDeserializable, DeserError, DeserResult, Deserializer},
-  stream_parser::{Token, TokenInfo},
+  deserialize::{feed_split, DefaultDeserializable, DeserError, DeserResult, Deserializer},
+  stream_parser::{CoarseToken, Token, TokenInfo},
 };
 
+/** Get the text of a number run which is only pushed to the list, `None` if it needs the tokens */
+fn get_digits<'a>(token: &CoarseToken<'a>, signed: bool) -> Option<&'a str> {
+  let is_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+  match *token {
+    CoarseToken::Number(
+      text,
+      TokenInfo::NumberHex | TokenInfo::NumberOct | TokenInfo::NumberBin,
+    ) => Some(text),
+    CoarseToken::Number(text, TokenInfo::NumberIntegerDigit) if is_digits(text) => Some(text),
+    CoarseToken::Number(text, TokenInfo::NumberIntegerSign)
+      if (signed || text.starts_with('+')) && is_digits(&text[1..]) =>
+    {
+      Some(text)
+    }
+    _ => None,
+  }
+}
+
 #[derive(Debug)]
 pub struct IntegerDeserializer {
   list: String,
@@ -63,6 +81,15 @@ macro_rules! signed_deserializer {
           }
         }
       }
+      fn feed_coarse(&mut self, token: CoarseToken) -> Result<DeserResult<$typ>, DeserError> {
+        match get_digits(&token, true) {
+          Some(text) => {
+            self.list.push_str(text);
+            Ok(DeserResult::Continue)
+          }
+          None => feed_split(token, |token| Deserializer::<$typ>::feed_token(self, token)),
+        }
+      }
     }
     impl DefaultDeserializable<$typ> for $typ {
       type DefaultDeserializer = IntegerDeserializer;
@@ -138,6 +165,15 @@ macro_rules! unsigned_deserializer {
           }
         }
       }
+      fn feed_coarse(&mut self, token: CoarseToken) -> Result<DeserResult<$typ>, DeserError> {
+        match get_digits(&token, false) {
+          Some(text) => {
+            self.list.push_str(text);
+            Ok(DeserResult::Continue)
+          }
+          None => feed_split(token, |token| Deserializer::<$typ>::feed_token(self, token)),
+        }
+      }
     }
     impl DefaultDeserializable<$typ> for $typ {
       type DefaultDeserializer = IntegerDeserializer;
@@ -1,8 +1,9 @@
 use crate::{
   deserialize::{
-    create_default_deserializer, DefaultDeserializable, DeserError, DeserResult, Deserializer,
+    create_default_deserializer, AnyToken, DefaultDeserializable, DeserError, DeserResult,
+    Deserializer,
   },
-  stream_parser::{Category, Token},
+  stream_parser::{Category, CoarseToken, Token},
   JsonArray, JsonObject, JsonValue,
 };
 
@@ -19,10 +20,10 @@ enum JsonSubdeserializer {
 pub struct JsonDeserializer {
   subdeser: Option<Box<JsonSubdeserializer>>,
 }
-impl Deserializer<JsonValue> for JsonDeserializer {
-  fn feed_token(&mut self, token: Token) -> Result<DeserResult<JsonValue>, DeserError> {
+impl JsonDeserializer {
+  fn feed<Tk: AnyToken>(&mut self, token: Tk) -> Result<DeserResult<JsonValue>, DeserError> {
     if self.subdeser.is_none() {
-      match token.info.get_category() {
+      match token.get_info().get_category() {
         Category::Whitespace | Category::Eof | Category::Comment => {
           return Ok(DeserResult::Continue);
         }
@@ -56,17 +57,23 @@ impl Deserializer<JsonValue> for JsonDeserializer {
       }
     }
     Ok(match self.subdeser.as_mut().unwrap().as_mut() {
-      JsonSubdeserializer::Null(deser) => deser.feed_token(token)?.map(|_| JsonValue::Null),
-      JsonSubdeserializer::Boolean(deser) => {
-        deser.feed_token(token)?.map(|v| JsonValue::Boolean(v))
-      }
-      JsonSubdeserializer::Number(deser) => deser.feed_token(token)?.map(|v| JsonValue::Number(v)),
-      JsonSubdeserializer::String(deser) => deser.feed_token(token)?.map(|v| JsonValue::String(v)),
-      JsonSubdeserializer::Array(deser) => deser.feed_token(token)?.map(|v| JsonValue::Array(v)),
-      JsonSubdeserializer::Object(deser) => deser.feed_token(token)?.map(|v| JsonValue::Object(v)),
+      JsonSubdeserializer::Null(deser) => token.feed_to(deser)?.map(|_| JsonValue::Null),
+      JsonSubdeserializer::Boolean(deser) => token.feed_to(deser)?.map(JsonValue::Boolean),
+      JsonSubdeserializer::Number(deser) => token.feed_to(deser)?.map(JsonValue::Number),
+      JsonSubdeserializer::String(deser) => token.feed_to(deser)?.map(JsonValue::String),
+      JsonSubdeserializer::Array(deser) => token.feed_to(deser)?.map(JsonValue::Array),
+      JsonSubdeserializer::Object(deser) => token.feed_to(deser)?.map(JsonValue::Object),
     })
   }
 }
+impl Deserializer<JsonValue> for JsonDeserializer {
+  fn feed_token(&mut self, token: Token) -> Result<DeserResult<JsonValue>, DeserError> {
+    self.feed(token)
+  }
+  fn feed_coarse(&mut self, token: CoarseToken) -> Result<DeserResult<JsonValue>, DeserError> {
+    self.feed(token)
+  }
+}
 impl DefaultDeserializable<JsonValue> for JsonValue {
   type DefaultDeserializer = JsonDeserializer;
   fn default_deserializer() -> Self::DefaultDeserializer {
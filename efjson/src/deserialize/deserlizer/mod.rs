@@ -4,6 +4,9 @@ mod integer;
 mod object;
 mod stateless;
 mod string;
+pub use string::{
+  create_lossy_string_deserializer, JsonWtf8String, StringReceiver, Wtf8StringReceiver,
+};
 
 mod box_rc;
 mod optional;
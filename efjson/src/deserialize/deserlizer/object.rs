@@ -26,14 +26,14 @@ where
   fn create_key(
     &mut self,
   ) -> Result<<Key as DefaultDeserializable<Key>>::DefaultDeserializer, DeserError> {
-    return Ok(Key::default_deserializer());
+    Ok(Key::default_deserializer())
   }
 
   fn create_value(
     &mut self,
     _key: &Key,
   ) -> Result<<Value as DefaultDeserializable<Value>>::DefaultDeserializer, DeserError> {
-    return Ok(Value::default_deserializer());
+    Ok(Value::default_deserializer())
   }
 
   fn set(&mut self, key: Key, value: Value) -> Result<(), DeserError> {
@@ -82,14 +82,14 @@ where
   fn create_key(
     &mut self,
   ) -> Result<<Key as DefaultDeserializable<Key>>::DefaultDeserializer, DeserError> {
-    return Ok(Key::default_deserializer());
+    Ok(Key::default_deserializer())
   }
 
   fn create_value(
     &mut self,
     _key: &Key,
   ) -> Result<<Value as DefaultDeserializable<Value>>::DefaultDeserializer, DeserError> {
-    return Ok(Value::default_deserializer());
+    Ok(Value::default_deserializer())
   }
 
   fn set(&mut self, key: Key, value: Value) -> Result<(), DeserError> {
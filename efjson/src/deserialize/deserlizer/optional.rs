@@ -1,6 +1,6 @@
 use crate::{
-  deserialize::{DefaultDeserializable, DeserError, DeserResult, Deserializer},
-  stream_parser::TokenInfo,
+  deserialize::{AnyToken, DefaultDeserializable, DeserError, DeserResult, Deserializer},
+  stream_parser::{CoarseToken, Token, TokenInfo},
 };
 
 #[derive(Debug)]
@@ -12,19 +12,16 @@ where
   started: bool,
   _phantom: std::marker::PhantomData<T>,
 }
-impl<T, Deser> Deserializer<Option<T>> for OptionReceiver<T, Deser>
+impl<T, Deser> OptionReceiver<T, Deser>
 where
   Deser: Deserializer<T>,
 {
-  fn feed_token(
-    &mut self,
-    token: crate::stream_parser::Token,
-  ) -> Result<DeserResult<Option<T>>, DeserError> {
+  fn feed<Tk: AnyToken>(&mut self, token: Tk) -> Result<DeserResult<Option<T>>, DeserError> {
     if !self.started {
       if token.is_space() {
         return Ok(DeserResult::Continue);
       }
-      match token.info {
+      match token.get_info() {
         TokenInfo::Null(_, done) => {
           return Ok(if done { DeserResult::Complete(None) } else { DeserResult::Continue });
         }
@@ -33,7 +30,18 @@ where
         }
       }
     }
-    Ok(self.deser.feed_token(token)?.map(|v| Some(v)))
+    Ok(token.feed_to(&mut self.deser)?.map(|v| Some(v)))
+  }
+}
+impl<T, Deser> Deserializer<Option<T>> for OptionReceiver<T, Deser>
+where
+  Deser: Deserializer<T>,
+{
+  fn feed_token(&mut self, token: Token) -> Result<DeserResult<Option<T>>, DeserError> {
+    self.feed(token)
+  }
+  fn feed_coarse(&mut self, token: CoarseToken) -> Result<DeserResult<Option<T>>, DeserError> {
+    self.feed(token)
   }
 }
 impl<T> DefaultDeserializable<Option<T>> for Option<T>
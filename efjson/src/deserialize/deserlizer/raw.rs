@@ -133,7 +133,7 @@ impl Deserializer<JsonRawToken> for JsonRawTokenDeserializer {
   ) -> Result<DeserResult<JsonRawToken>, DeserError> {
     match self.stage {
       StageEnum::NotStarted => {
-        self.tokens.push(token.clone());
+        self.tokens.push(token);
         match token.info.get_category() {
           Category::String => {
             self.stage = StageEnum::String;
@@ -162,7 +162,7 @@ impl Deserializer<JsonRawToken> for JsonRawTokenDeserializer {
         }
       }
       StageEnum::Stateless => {
-        self.tokens.push(token.clone());
+        self.tokens.push(token);
         match token.info {
           TokenInfo::Null(_, done) | TokenInfo::True(_, done) | TokenInfo::False(_, done) => {
             if done {
@@ -176,7 +176,7 @@ impl Deserializer<JsonRawToken> for JsonRawTokenDeserializer {
       }
       StageEnum::Number => {
         if matches!(token.info.get_category(), Category::Number) {
-          self.tokens.push(token.clone());
+          self.tokens.push(token);
           Ok(DeserResult::Continue)
         } else {
           Ok(DeserResult::CompleteWithRollback(JsonRawToken {
@@ -185,7 +185,7 @@ impl Deserializer<JsonRawToken> for JsonRawTokenDeserializer {
         }
       }
       StageEnum::String => {
-        self.tokens.push(token.clone());
+        self.tokens.push(token);
         if matches!(token.info, TokenInfo::StringEnd) {
           Ok(DeserResult::Complete(JsonRawToken { tokens: std::mem::take(&mut self.tokens) }))
         } else {
@@ -193,7 +193,7 @@ impl Deserializer<JsonRawToken> for JsonRawTokenDeserializer {
         }
       }
       StageEnum::Structure => {
-        self.tokens.push(token.clone());
+        self.tokens.push(token);
         match token.info {
           TokenInfo::ArrayStart | TokenInfo::ObjectStart => {
             self.cnt += 1;
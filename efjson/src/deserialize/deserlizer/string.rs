@@ -6,6 +6,17 @@ use crate::deserialize::{
 #[derive(Debug)]
 pub struct StringReceiver {
   pub string: String,
+  /** whether to replace lone surrogates with U+FFFD (otherwise they are rejected) */
+  pub lossy: bool,
+}
+impl StringReceiver {
+  fn push_lossy(&mut self) -> Result<(), DeserError> {
+    if !self.lossy {
+      return Err("lone surrogate in string".into());
+    }
+    self.string.push(char::REPLACEMENT_CHARACTER);
+    Ok(())
+  }
 }
 
 impl StringReceiverTrait<String> for StringReceiver {
@@ -16,6 +27,13 @@ impl StringReceiverTrait<String> for StringReceiver {
     self.string.push(c);
     Ok(())
   }
+  fn push_str(&mut self, s: &str) -> Result<(), DeserError> {
+    self.string.push_str(s);
+    Ok(())
+  }
+  fn push_surrogate(&mut self, _unit: u16) -> Result<(), DeserError> {
+    self.push_lossy()
+  }
   fn end(&mut self) -> Result<String, DeserError> {
     Ok(std::mem::take(&mut self.string))
   }
@@ -23,7 +41,7 @@ impl StringReceiverTrait<String> for StringReceiver {
 impl DefaultDeserializable<String> for String {
   type DefaultDeserializer = StringReceiverDeserializer<String, StringReceiver>;
   fn default_deserializer() -> Self::DefaultDeserializer {
-    create_string_deserializer(StringReceiver { string: String::new() })
+    create_string_deserializer(StringReceiver { string: String::new(), lossy: false })
   }
 }
 
@@ -35,6 +53,13 @@ impl StringReceiverTrait<Box<str>> for StringReceiver {
     self.string.push(c);
     Ok(())
   }
+  fn push_str(&mut self, s: &str) -> Result<(), DeserError> {
+    self.string.push_str(s);
+    Ok(())
+  }
+  fn push_surrogate(&mut self, _unit: u16) -> Result<(), DeserError> {
+    self.push_lossy()
+  }
   fn end(&mut self) -> Result<Box<str>, DeserError> {
     Ok(std::mem::take(&mut self.string).into())
   }
@@ -42,6 +67,167 @@ impl StringReceiverTrait<Box<str>> for StringReceiver {
 impl DefaultDeserializable<Box<str>> for Box<str> {
   type DefaultDeserializer = StringReceiverDeserializer<Box<str>, StringReceiver>;
   fn default_deserializer() -> Self::DefaultDeserializer {
-    create_string_deserializer(StringReceiver { string: String::new() })
+    create_string_deserializer(StringReceiver { string: String::new(), lossy: false })
+  }
+}
+
+/**
+Create a deserializer of `String` which replaces lone surrogates with U+FFFD,
+see `ParserOption::LONE_SURROGATE` (the default deserializer rejects them).
+
+# Example
+```rust
+use efjson::{deserialize::*, stream_parser::StreamParser, ParserOption};
+let input = "\"\\uD83D\\uDE00\\uD83D!\"";
+let tokens = StreamParser::create_iter(ParserOption::LONE_SURROGATE, input.chars()).map(Result::unwrap);
+let string = unwrap_deser_result(create_lossy_string_deserializer().feed_token_iter(tokens)).unwrap();
+#[cfg(not(feature = "no-combine-surrogate"))]
+assert_eq!(string, "😀\u{FFFD}!");
+assert!(deserialize::<String>(ParserOption::LONE_SURROGATE, input).is_err());
+```
+*/
+pub fn create_lossy_string_deserializer() -> StringReceiverDeserializer<String, StringReceiver> {
+  create_string_deserializer(StringReceiver { string: String::new(), lossy: true })
+}
+
+/**
+A string which may contain lone surrogates (accepted with `ParserOption::LONE_SURROGATE`),
+stored as WTF-8 (UTF-8 generalized to encode surrogates, where surrogate pairs are always joined).
+
+It can be converted back to UTF-16 losslessly, as JavaScript strings.
+
+# Example
+```rust
+use efjson::{deserialize::{deserialize, JsonWtf8String}, ParserOption};
+let string: JsonWtf8String = deserialize(ParserOption::LONE_SURROGATE, "\"a\\uDE00\\uD83D\"").unwrap();
+assert_eq!(string.encode_utf16(), [0x61, 0xDE00, 0xD83D]);
+assert_eq!(string.as_str(), None);
+assert_eq!(string.to_string_lossy(), "a\u{FFFD}\u{FFFD}");
+
+let string: JsonWtf8String = deserialize(ParserOption::LONE_SURROGATE, "\"\\uD83D\\uDE00\"").unwrap();
+assert_eq!(string.into_string().unwrap(), "😀");
+```
+*/
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct JsonWtf8String {
+  bytes: Vec<u8>,
+}
+impl JsonWtf8String {
+  pub fn new() -> Self {
+    Self::default()
+  }
+  pub fn push(&mut self, c: char) {
+    let mut buf = [0u8; 4];
+    self.bytes.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+  }
+  pub fn push_str(&mut self, s: &str) {
+    self.bytes.extend_from_slice(s.as_bytes());
+  }
+  /** Push a UTF-16 code unit, a low surrogate right after a high surrogate is joined with it */
+  pub fn push_code_unit(&mut self, unit: u16) {
+    if let Some(c) = char::from_u32(unit as u32) {
+      return self.push(c);
+    }
+    if unit >= 0xDC00 {
+      if let Some(&[0xED, b1 @ 0xA0..=0xAF, b2]) = self.bytes.last_chunk::<3>() {
+        let high = 0xD000 | (b1 as u32 & 0x3F) << 6 | (b2 as u32 & 0x3F);
+        let u = ((high & 0x3FF) << 10 | (unit as u32 & 0x3FF)) + 0x10000;
+        self.bytes.truncate(self.bytes.len() - 3);
+        return self.push(unsafe { char::from_u32_unchecked(u) });
+      }
+    }
+    self.bytes.extend_from_slice(&[
+      0xE0 | (unit >> 12) as u8,
+      0x80 | (unit >> 6 & 0x3F) as u8,
+      0x80 | (unit & 0x3F) as u8,
+    ]);
+  }
+  /** Get the WTF-8 bytes */
+  pub fn as_bytes(&self) -> &[u8] {
+    &self.bytes
+  }
+  /** Get the string if it contains no lone surrogates */
+  pub fn as_str(&self) -> Option<&str> {
+    std::str::from_utf8(&self.bytes).ok()
+  }
+  /** Convert to `String` if it contains no lone surrogates */
+  pub fn into_string(self) -> Result<String, Self> {
+    String::from_utf8(self.bytes).map_err(|err| Self { bytes: err.into_bytes() })
+  }
+  /** Convert to `String`, replacing lone surrogates with U+FFFD */
+  pub fn to_string_lossy(&self) -> String {
+    self.code_points().map(|u| char::from_u32(u).unwrap_or(char::REPLACEMENT_CHARACTER)).collect()
+  }
+  /** Encode as UTF-16, including the lone surrogates */
+  pub fn encode_utf16(&self) -> Vec<u16> {
+    let mut units = Vec::with_capacity(self.bytes.len());
+    for u in self.code_points() {
+      match char::from_u32(u) {
+        Some(c) => units.extend_from_slice(c.encode_utf16(&mut [0; 2])),
+        None => units.push(u as u16),
+      }
+    }
+    units
+  }
+  /** Iterate over the code points (lone surrogates included) */
+  fn code_points(&self) -> impl Iterator<Item = u32> + '_ {
+    let mut bytes = self.bytes.iter().copied();
+    std::iter::from_fn(move || {
+      let first = bytes.next()? as u32;
+      let (len, init) = match first {
+        0x00..=0x7F => return Some(first),
+        0xC0..=0xDF => (1, first & 0x1F),
+        0xE0..=0xEF => (2, first & 0x0F),
+        _ => (3, first & 0x07),
+      };
+      Some((0..len).fold(init, |u, _| u << 6 | (bytes.next().unwrap_or(0x80) as u32 & 0x3F)))
+    })
+  }
+}
+impl From<String> for JsonWtf8String {
+  fn from(string: String) -> Self {
+    Self { bytes: string.into_bytes() }
+  }
+}
+impl From<&str> for JsonWtf8String {
+  fn from(string: &str) -> Self {
+    Self { bytes: string.as_bytes().to_vec() }
+  }
+}
+impl std::fmt::Display for JsonWtf8String {
+  /** Write the string, lone surrogates are written as U+FFFD */
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.write_str(&self.to_string_lossy())
+  }
+}
+
+#[derive(Debug)]
+pub struct Wtf8StringReceiver {
+  pub string: JsonWtf8String,
+}
+impl StringReceiverTrait<JsonWtf8String> for Wtf8StringReceiver {
+  fn start(&mut self) -> Result<(), DeserError> {
+    Ok(())
+  }
+  fn push(&mut self, c: char) -> Result<(), DeserError> {
+    self.string.push(c);
+    Ok(())
+  }
+  fn push_str(&mut self, s: &str) -> Result<(), DeserError> {
+    self.string.push_str(s);
+    Ok(())
+  }
+  fn push_surrogate(&mut self, unit: u16) -> Result<(), DeserError> {
+    self.string.push_code_unit(unit);
+    Ok(())
+  }
+  fn end(&mut self) -> Result<JsonWtf8String, DeserError> {
+    Ok(std::mem::take(&mut self.string))
+  }
+}
+impl DefaultDeserializable<JsonWtf8String> for JsonWtf8String {
+  type DefaultDeserializer = StringReceiverDeserializer<JsonWtf8String, Wtf8StringReceiver>;
+  fn default_deserializer() -> Self::DefaultDeserializer {
+    create_string_deserializer(Wtf8StringReceiver { string: JsonWtf8String::new() })
   }
 }
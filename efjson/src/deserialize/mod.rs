@@ -1,7 +1,11 @@
 use crate::{
-  stream_parser::{StreamParser, Token},
+  stream_parser::{
+    CoarseToken, DocumentToken, JsonSeqParser, Limits, MultiDocumentParser, StreamParser, Token,
+    TokenInfo, TokenSink,
+  },
   ParserOption,
 };
+use std::ops::ControlFlow;
 
 #[derive(Debug)]
 pub enum DeserResult<Result> {
@@ -36,6 +40,130 @@ pub trait Deserializer<T> {
     }
     Ok(DeserResult::Continue)
   }
+
+  /**
+  Feed a coarse token (see `StreamParser::feed_coarse`).
+
+  `DeserResult::Complete` means the whole token is consumed,
+  and `DeserResult::CompleteWithRollback` means none of it is.
+  By default, the token is split and fed by `feed_token`.
+
+  # Errors
+  The errors of `feed_token`, or an error if the deserialization completes inside a run.
+  */
+  fn feed_coarse(&mut self, token: CoarseToken) -> Result<DeserResult<T>, DeserError> {
+    feed_split(token, |token| self.feed_token(token))
+  }
+}
+
+/**
+Feed the tokens of `StreamParser::feed_to` to a deserializer,
+which breaks with the result once the value is complete (or with the error).
+
+`DeserResult::CompleteWithRollback` means the token it breaks on is not a part of the value,
+for example the character after a number at the root, though the parser has consumed the character.
+A deserializer never breaks on a virtual comma (see `StreamParser::feed_to`), which is never at the root.
+
+# Example
+```rust
+use efjson::{deserialize::*, stream_parser::StreamParser, ParserOption};
+use std::ops::ControlFlow;
+let mut parser = StreamParser::new(ParserOption::empty());
+let mut deserializer = create_default_deserializer::<Vec<i32>>();
+let flow = parser.feed_to("[1, 2] this is not parsed", &mut deserializer).unwrap();
+let ControlFlow::Break((Ok(DeserResult::Complete(value)), None)) = flow else { unreachable!() };
+assert_eq!(value, [1, 2]);
+assert_eq!(parser.get_position(), 6);
+
+/* the number is complete at the space after it */
+let mut parser = StreamParser::new(ParserOption::empty());
+let mut deserializer = create_default_deserializer::<i32>();
+let flow = parser.feed_to("12 34", &mut deserializer).unwrap();
+let ControlFlow::Break((Ok(DeserResult::CompleteWithRollback(value)), None)) = flow else {
+  unreachable!()
+};
+assert_eq!((value, parser.get_position()), (12, 3));
+
+/* the virtual comma is inside the array */
+let mut parser = StreamParser::new(ParserOption::MISSING_COMMA);
+let mut deserializer = create_default_deserializer::<Vec<i32>>();
+let flow = parser.feed_to("[1 2]", &mut deserializer).unwrap();
+let ControlFlow::Break((Ok(DeserResult::Complete(value)), None)) = flow else { unreachable!() };
+assert_eq!(value, [1, 2]);
+```
+*/
+impl<T, D: Deserializer<T>> TokenSink<Result<DeserResult<T>, DeserError>> for D {
+  fn token(&mut self, token: Token) -> ControlFlow<Result<DeserResult<T>, DeserError>> {
+    match self.feed_token(token) {
+      Ok(DeserResult::Continue) => ControlFlow::Continue(()),
+      result => ControlFlow::Break(result),
+    }
+  }
+}
+
+/** Feed the tokens split from a coarse token, see `Deserializer::feed_coarse` */
+pub(crate) fn feed_split<T>(
+  token: CoarseToken,
+  mut feed: impl FnMut(Token) -> Result<DeserResult<T>, DeserError>,
+) -> Result<DeserResult<T>, DeserError> {
+  let mut tokens = token.tokens().peekable();
+  let mut first = true;
+  while let Some(token) = tokens.next() {
+    match feed(token)? {
+      DeserResult::Continue => {}
+      DeserResult::CompleteWithRollback(v) if first => {
+        return Ok(DeserResult::CompleteWithRollback(v))
+      }
+      DeserResult::Complete(v) if tokens.peek().is_none() => return Ok(DeserResult::Complete(v)),
+      _ => return Err("deserialization completed inside a run".into()),
+    }
+    first = false;
+  }
+  Ok(DeserResult::Continue)
+}
+
+/** A token or a coarse token, so that containers forward both in the same way */
+pub(crate) trait AnyToken: Copy {
+  fn is_space(&self) -> bool;
+  /** Get the token of the (first) character */
+  fn get_info(&self) -> TokenInfo;
+  fn feed_to<T, D: Deserializer<T> + ?Sized>(
+    self,
+    deserializer: &mut D,
+  ) -> Result<DeserResult<T>, DeserError>;
+}
+impl AnyToken for Token {
+  fn is_space(&self) -> bool {
+    Token::is_space(self)
+  }
+  fn get_info(&self) -> TokenInfo {
+    self.info
+  }
+  fn feed_to<T, D: Deserializer<T> + ?Sized>(
+    self,
+    deserializer: &mut D,
+  ) -> Result<DeserResult<T>, DeserError> {
+    deserializer.feed_token(self)
+  }
+}
+impl AnyToken for CoarseToken<'_> {
+  fn is_space(&self) -> bool {
+    CoarseToken::is_space(self)
+  }
+  fn get_info(&self) -> TokenInfo {
+    match *self {
+      CoarseToken::Single(token) => token.info,
+      CoarseToken::String(_) => TokenInfo::StringNormal,
+      CoarseToken::Whitespace(_) => TokenInfo::Whitespace,
+      CoarseToken::Comment(_, info) | CoarseToken::Number(_, info) => info,
+    }
+  }
+  fn feed_to<T, D: Deserializer<T> + ?Sized>(
+    self,
+    deserializer: &mut D,
+  ) -> Result<DeserResult<T>, DeserError> {
+    deserializer.feed_coarse(self)
+  }
 }
 
 pub fn unwrap_deser_result<T>(result: Result<DeserResult<T>, DeserError>) -> Result<T, DeserError> {
@@ -54,12 +182,11 @@ pub fn create_default_deserializer<T: DefaultDeserializable<T>>(
   T::default_deserializer()
 }
 
-pub fn deserialize<T: DefaultDeserializable<T>>(
-  option: ParserOption,
-  s: &str,
+fn deserialize_from<T: DefaultDeserializable<T>, E: Into<DeserError>>(
+  tokens: impl Iterator<Item = Result<Token, E>>,
 ) -> Result<T, DeserError> {
   let mut deserializer = create_default_deserializer::<T>();
-  for token in StreamParser::create_iter(option, s.chars()) {
+  for token in tokens {
     match deserializer.feed_token(token.map_err(|e| -> DeserError { e.into() })?) {
       Ok(res) => match res {
         DeserResult::Complete(v) | DeserResult::CompleteWithRollback(v) => return Ok(v),
@@ -70,6 +197,86 @@ pub fn deserialize<T: DefaultDeserializable<T>>(
   }
   Err("incomplete deserialization".into())
 }
+pub fn deserialize<T: DefaultDeserializable<T>>(
+  option: ParserOption,
+  s: &str,
+) -> Result<T, DeserError> {
+  deserialize_from(StreamParser::create_iter(option, s.chars()))
+}
+/**
+Deserialize with limits, see `StreamParser::with_limits`.
+*/
+pub fn deserialize_with_limits<T: DefaultDeserializable<T>>(
+  option: ParserOption,
+  limits: Limits,
+  s: &str,
+) -> Result<T, DeserError> {
+  let mut parser = StreamParser::with_limits(option, limits);
+  deserialize_from(
+    s.chars().chain(std::iter::once('\0')).flat_map(move |c| parser.feed_pair_iter(c)).flatten(),
+  )
+}
+/**
+Deserialize with coarse tokens, see `StreamParser::feed_coarse` and `Deserializer::feed_coarse`.
+
+Strings and numbers are pushed as slices rather than character by character.
+
+# Note
+The whole input is parsed before deserialization, so errors after the value are reported as well.
+
+# Example
+```rust
+use efjson::{deserialize::deserialize_coarse, ParserOption};
+let value: Vec<(String, i32)> =
+  deserialize_coarse(ParserOption::empty(), "[[\"a b\", 12], [\"c\\n\", -3]]").unwrap();
+assert_eq!(value, [("a b".to_string(), 12), ("c\n".to_string(), -3)]);
+assert!(deserialize_coarse::<i32>(ParserOption::empty(), "1 2").is_err());
+```
+*/
+pub fn deserialize_coarse<T: DefaultDeserializable<T>>(
+  option: ParserOption,
+  s: &str,
+) -> Result<T, DeserError> {
+  let mut parser = StreamParser::new(option);
+  let mut tokens = parser.feed_coarse(s)?;
+  tokens.extend(parser.feed_coarse("\0")?);
+  let mut deserializer = create_default_deserializer::<T>();
+  for token in tokens {
+    match deserializer.feed_coarse(token)? {
+      DeserResult::Complete(v) | DeserResult::CompleteWithRollback(v) => return Ok(v),
+      DeserResult::Continue => {}
+    }
+  }
+  Err("incomplete deserialization".into())
+}
+/**
+Deserialize from UTF-8 bytes, invalid input is reported as `ErrorKind::InvalidInputUtf`.
+*/
+pub fn deserialize_bytes<T: DefaultDeserializable<T>>(
+  option: ParserOption,
+  bytes: &[u8],
+) -> Result<T, DeserError> {
+  deserialize_from(StreamParser::create_bytes_iter(option, bytes.iter().copied()))
+}
+/**
+Deserialize from UTF-8 input of a reader, see `StreamParser::from_reader`.
+
+I/O errors are returned as `ReaderError::IoError`, which can be got by downcasting the error.
+*/
+pub fn from_reader<T: DefaultDeserializable<T>>(
+  option: ParserOption,
+  reader: impl std::io::Read,
+) -> Result<T, DeserError> {
+  deserialize_from(StreamParser::from_reader(option, reader))
+}
+/** The same as `from_reader`, but with limits, see `StreamParser::with_limits` */
+pub fn from_reader_with_limits<T: DefaultDeserializable<T>>(
+  option: ParserOption,
+  limits: Limits,
+  reader: impl std::io::Read,
+) -> Result<T, DeserError> {
+  deserialize_from(StreamParser::from_reader_with_limits(option, limits, reader))
+}
 pub fn deserialize_tokens<T: DefaultDeserializable<T>>(
   tokens: impl Iterator<Item = Token>,
 ) -> Result<T, DeserError> {
@@ -77,6 +284,103 @@ pub fn deserialize_tokens<T: DefaultDeserializable<T>>(
   unwrap_deser_result(deserializer.feed_token_iter(tokens))
 }
 
+/** Deserialize each document of the tokens, see `iter_documents` */
+struct DocumentIter<T: DefaultDeserializable<T>, I> {
+  tokens: I,
+  document: usize,
+  deserializer: Option<T::DefaultDeserializer>,
+  /** whether the result of the current document has been yielded */
+  done: bool,
+}
+impl<T, I, E> Iterator for DocumentIter<T, I>
+where
+  T: DefaultDeserializable<T>,
+  I: Iterator<Item = Result<DocumentToken, E>>,
+  E: Into<DeserError>,
+{
+  type Item = Result<T, DeserError>;
+  fn next(&mut self) -> Option<Self::Item> {
+    loop {
+      let DocumentToken { document, token } = match self.tokens.next()? {
+        Ok(token) => token,
+        Err(err) => {
+          self.deserializer = None;
+          self.done = true;
+          return Some(Err(err.into()));
+        }
+      };
+      if document != self.document {
+        self.document = document;
+        self.deserializer = None;
+        self.done = false;
+      }
+      if self.done {
+        continue;
+      }
+      let deserializer = match self.deserializer.as_mut() {
+        Some(deserializer) => deserializer,
+        None if token.is_space() => continue,
+        None => self.deserializer.insert(create_default_deserializer::<T>()),
+      };
+      let result = match deserializer.feed_token(token) {
+        Ok(DeserResult::Continue) => continue,
+        Ok(DeserResult::Complete(v) | DeserResult::CompleteWithRollback(v)) => Ok(v),
+        Err(err) => Err(err),
+      };
+      self.deserializer = None;
+      self.done = true;
+      return Some(result);
+    }
+  }
+}
+/**
+Deserialize a sequence of JSON values (for example, newline-delimited JSON),
+see `MultiDocumentParser`.
+
+The iterator yields a result for each document, an error does not stop the iteration.
+
+# Example
+```rust
+use efjson::{deserialize::iter_documents, ParserOption};
+let input = "1\n[2]\n3 4";
+let results: Vec<_> = iter_documents::<i32>(ParserOption::empty(), input).collect();
+assert_eq!(results.len(), 4);
+assert!(results[1].is_err());
+assert_eq!(results.into_iter().filter_map(Result::ok).collect::<Vec<_>>(), [1, 3, 4]);
+```
+*/
+pub fn iter_documents<'a, T: DefaultDeserializable<T> + 'a>(
+  option: ParserOption,
+  input: &'a str,
+) -> impl Iterator<Item = Result<T, DeserError>> + 'a {
+  DocumentIter::<T, _> {
+    tokens: MultiDocumentParser::create_iter(option, input.chars()),
+    document: 0,
+    deserializer: None,
+    done: false,
+  }
+}
+/**
+Deserialize a JSON text sequence (RFC 7464, `application/json-seq`), see `JsonSeqParser`.
+
+The iterator yields a result for each text, truncated texts are skipped.
+
+# Example
+```rust
+use efjson::{deserialize::iter_json_seq, ParserOption};
+let input = "\u{1E}[1]\n\u{1E}[2,\u{1E}[3]\n";
+let results: Vec<Vec<i32>> = iter_json_seq(ParserOption::empty(), input).map(Result::unwrap).collect();
+assert_eq!(results, [[1], [3]]);
+```
+*/
+pub fn iter_json_seq<'a, T: DefaultDeserializable<T> + 'a>(
+  option: ParserOption,
+  input: &'a str,
+) -> impl Iterator<Item = Result<T, DeserError>> + 'a {
+  JsonSeqParser::create_iter(option, input.chars())
+    .map(|text| deserialize_tokens(text?.into_iter()))
+}
+
 mod receiver;
 pub use receiver::*;
 mod deserlizer;
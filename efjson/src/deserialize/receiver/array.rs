@@ -1,6 +1,6 @@
 use crate::{
-  deserialize::{DeserError, DeserResult, Deserializer},
-  stream_parser::{Token, TokenInfo},
+  deserialize::{AnyToken, DeserError, DeserResult, Deserializer},
+  stream_parser::{CoarseToken, Token, TokenInfo},
 };
 use std::{marker::PhantomData, mem::MaybeUninit};
 
@@ -18,6 +18,18 @@ enum StageEnum {
   ElementEnd,
   End,
 }
+/**
+Deserialize an array element by element, see `ArrayReceiverTrait`.
+
+# Example
+```rust
+use efjson::{deserialize::deserialize, ParserOption};
+let v: Vec<Vec<i32>> = deserialize(ParserOption::empty(), "[ [1 ,2 ] , [] ]").unwrap();
+assert_eq!(v, vec![vec![1, 2], vec![]]);
+let v: Vec<bool> = deserialize(ParserOption::empty(), "[true\n, false\n]").unwrap();
+assert_eq!(v, vec![true, false]);
+```
+*/
 #[derive(Debug)]
 pub struct ArrayReceiverDeserializer<Element, Return, Receiver, SubDeserializer>
 where
@@ -29,15 +41,15 @@ where
   stage: StageEnum,
   _phantom: PhantomData<(Return, Element)>,
 }
-impl<Element, Return, Receiver, SubDeserializer> Deserializer<Return>
-  for ArrayReceiverDeserializer<Element, Return, Receiver, SubDeserializer>
+impl<Element, Return, Receiver, SubDeserializer>
+  ArrayReceiverDeserializer<Element, Return, Receiver, SubDeserializer>
 where
   Receiver: ArrayReceiverTrait<Element, Return, SubDeserializer>,
   SubDeserializer: Deserializer<Element>,
 {
-  fn feed_token(&mut self, token: Token) -> Result<DeserResult<Return>, DeserError> {
+  fn feed<Tk: AnyToken>(&mut self, token: Tk) -> Result<DeserResult<Return>, DeserError> {
     if matches!(self.stage, StageEnum::Element) {
-      match unsafe { self.subreceiver.assume_init_mut() }.feed_token(token)? {
+      match token.feed_to(unsafe { self.subreceiver.assume_init_mut() })? {
         DeserResult::Complete(elem) => {
           unsafe { self.subreceiver.assume_init_drop() };
           self.receiver.append(elem)?;
@@ -55,14 +67,14 @@ where
     }
     if matches!(self.stage, StageEnum::WaitElement) {
       if !token.is_space() {
-        if matches!(token.info, TokenInfo::ArrayEnd) {
+        if matches!(token.get_info(), TokenInfo::ArrayEnd) {
           // trailing comma
           self.stage = StageEnum::End;
           return Ok(DeserResult::Complete(self.receiver.end()?));
         }
         self.subreceiver.write(self.receiver.create_element()?);
         self.stage = StageEnum::Element;
-        match unsafe { self.subreceiver.assume_init_mut() }.feed_token(token)? {
+        match token.feed_to(unsafe { self.subreceiver.assume_init_mut() })? {
           DeserResult::Complete(elem) => {
             unsafe { self.subreceiver.assume_init_drop() };
             self.receiver.append(elem)?;
@@ -75,7 +87,7 @@ where
       return Ok(DeserResult::Continue);
     }
 
-    match token.info {
+    match token.get_info() {
       TokenInfo::ArrayStart => {
         assert!(matches!(self.stage, StageEnum::NotStarted));
         self.stage = StageEnum::WaitElement;
@@ -91,10 +103,29 @@ where
         self.stage = StageEnum::WaitElement;
         Ok(DeserResult::Continue)
       }
-      _ => Err("expect array".into()),
+      _ => {
+        if token.is_space() {
+          Ok(DeserResult::Continue)
+        } else {
+          Err("expect array".into())
+        }
+      }
     }
   }
 }
+impl<Element, Return, Receiver, SubDeserializer> Deserializer<Return>
+  for ArrayReceiverDeserializer<Element, Return, Receiver, SubDeserializer>
+where
+  Receiver: ArrayReceiverTrait<Element, Return, SubDeserializer>,
+  SubDeserializer: Deserializer<Element>,
+{
+  fn feed_token(&mut self, token: Token) -> Result<DeserResult<Return>, DeserError> {
+    self.feed(token)
+  }
+  fn feed_coarse(&mut self, token: CoarseToken) -> Result<DeserResult<Return>, DeserError> {
+    self.feed(token)
+  }
+}
 impl<Element, Return, Receiver, SubDeserializer> Drop
   for ArrayReceiverDeserializer<Element, Return, Receiver, SubDeserializer>
 where
@@ -1,6 +1,6 @@
 use crate::{
-  deserialize::{DeserError, DeserResult, Deserializer},
-  stream_parser::{Token, TokenInfo},
+  deserialize::{AnyToken, DeserError, DeserResult, Deserializer},
+  stream_parser::{CoarseToken, Token, TokenInfo},
 };
 use std::{marker::PhantomData, mem::MaybeUninit};
 
@@ -49,17 +49,17 @@ pub struct ObjectReceiverDeserializer<
   stage: StageEnum,
   _phantom: PhantomData<(Return, Value)>,
 }
-impl<Key, Value, Return, Receiver, KeyDeserializer, ValueDeserializer> Deserializer<Return>
-  for ObjectReceiverDeserializer<Key, Value, Return, Receiver, KeyDeserializer, ValueDeserializer>
+impl<Key, Value, Return, Receiver, KeyDeserializer, ValueDeserializer>
+  ObjectReceiverDeserializer<Key, Value, Return, Receiver, KeyDeserializer, ValueDeserializer>
 where
   Receiver: ObjectReceiverTrait<Key, Value, Return, KeyDeserializer, ValueDeserializer>,
   KeyDeserializer: Deserializer<Key>,
   ValueDeserializer: Deserializer<Value>,
 {
-  fn feed_token(&mut self, token: Token) -> Result<DeserResult<Return>, DeserError> {
+  fn feed<Tk: AnyToken>(&mut self, token: Tk) -> Result<DeserResult<Return>, DeserError> {
     match self.stage {
       StageEnum::Key => {
-        match unsafe { self.key_subreceiver.assume_init_mut() }.feed_token(token)? {
+        match token.feed_to(unsafe { self.key_subreceiver.assume_init_mut() })? {
           DeserResult::Complete(key) => {
             unsafe { self.key_subreceiver.assume_init_drop() };
             self.key.write(key);
@@ -76,7 +76,7 @@ where
         }
       }
       StageEnum::Value => {
-        match unsafe { self.value_subreceiver.assume_init_mut() }.feed_token(token)? {
+        match token.feed_to(unsafe { self.value_subreceiver.assume_init_mut() })? {
           DeserResult::Complete(value) => {
             unsafe { self.value_subreceiver.assume_init_drop() };
             self.stage = StageEnum::ValueEnd;
@@ -96,7 +96,7 @@ where
     }
     if matches!(self.stage, StageEnum::WaitKey) {
       if !token.is_space() {
-        if matches!(token.info, TokenInfo::ObjectEnd) {
+        if matches!(token.get_info(), TokenInfo::ObjectEnd) {
           // trailing comma
           self.stage = StageEnum::End;
           return Ok(DeserResult::Complete(self.receiver.end()?));
@@ -105,7 +105,7 @@ where
         self.key_subreceiver.write(self.receiver.create_key()?);
         self.stage = StageEnum::Key;
 
-        match unsafe { self.key_subreceiver.assume_init_mut() }.feed_token(token)? {
+        match token.feed_to(unsafe { self.key_subreceiver.assume_init_mut() })? {
           DeserResult::Complete(key) => {
             unsafe { self.key_subreceiver.assume_init_drop() };
             self.key.write(key);
@@ -124,7 +124,7 @@ where
           .value_subreceiver
           .write(self.receiver.create_value(unsafe { self.key.assume_init_ref() })?);
 
-        match unsafe { self.value_subreceiver.assume_init_mut() }.feed_token(token)? {
+        match token.feed_to(unsafe { self.value_subreceiver.assume_init_mut() })? {
           DeserResult::Complete(value) => {
             unsafe { self.value_subreceiver.assume_init_drop() };
             self.stage = StageEnum::ValueEnd;
@@ -137,7 +137,7 @@ where
       return Ok(DeserResult::Continue);
     }
 
-    match token.info {
+    match token.get_info() {
       TokenInfo::ObjectStart => {
         assert!(matches!(self.stage, StageEnum::NotStarted));
         self.stage = StageEnum::WaitKey;
@@ -167,6 +167,20 @@ where
     }
   }
 }
+impl<Key, Value, Return, Receiver, KeyDeserializer, ValueDeserializer> Deserializer<Return>
+  for ObjectReceiverDeserializer<Key, Value, Return, Receiver, KeyDeserializer, ValueDeserializer>
+where
+  Receiver: ObjectReceiverTrait<Key, Value, Return, KeyDeserializer, ValueDeserializer>,
+  KeyDeserializer: Deserializer<Key>,
+  ValueDeserializer: Deserializer<Value>,
+{
+  fn feed_token(&mut self, token: Token) -> Result<DeserResult<Return>, DeserError> {
+    self.feed(token)
+  }
+  fn feed_coarse(&mut self, token: CoarseToken) -> Result<DeserResult<Return>, DeserError> {
+    self.feed(token)
+  }
+}
 
 impl<Key, Value, Return, Receiver, KeyDeserializer, ValueDeserializer> Drop
   for ObjectReceiverDeserializer<Key, Value, Return, Receiver, KeyDeserializer, ValueDeserializer>
@@ -1,11 +1,25 @@
 use crate::{
-  deserialize::{DeserError, DeserResult, Deserializer},
-  stream_parser::{Token, TokenInfo},
+  deserialize::{feed_split, DeserError, DeserResult, Deserializer},
+  stream_parser::{CoarseToken, Token, TokenInfo},
 };
 
 pub trait StringReceiverTrait<Return> {
   fn start(&mut self) -> Result<(), DeserError>;
   fn push(&mut self, c: char) -> Result<(), DeserError>;
+  /** Push unescaped characters (see `Deserializer::feed_coarse`), pushed one by one by default */
+  fn push_str(&mut self, s: &str) -> Result<(), DeserError> {
+    s.chars().try_for_each(|c| self.push(c))
+  }
+  /**
+  Push a lone surrogate (accepted with `ParserOption::LONE_SURROGATE`),
+  escaped surrogate pairs are pushed as characters.
+
+  Lone surrogates are rejected by default.
+  */
+  fn push_surrogate(&mut self, unit: u16) -> Result<(), DeserError> {
+    let _ = unit;
+    Err("lone surrogate in string".into())
+  }
   fn end(&mut self) -> Result<Return, DeserError>;
 }
 
@@ -46,6 +60,10 @@ where
         }
         Ok(DeserResult::Continue)
       }
+      TokenInfo::StringEscapeSurrogate(unit) => {
+        self.receiver.push_surrogate(unit)?;
+        Ok(DeserResult::Continue)
+      }
       _ => {
         if token.is_space() {
           Ok(DeserResult::Continue)
@@ -55,6 +73,15 @@ where
       }
     }
   }
+  fn feed_coarse(&mut self, token: CoarseToken) -> Result<DeserResult<Return>, DeserError> {
+    match token {
+      CoarseToken::String(text) => {
+        self.receiver.push_str(text)?;
+        Ok(DeserResult::Continue)
+      }
+      token => feed_split(token, |token| self.feed_token(token)),
+    }
+  }
 }
 
 pub fn create_string_deserializer<Return, Receiver>(
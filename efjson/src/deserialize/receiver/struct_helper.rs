@@ -2,17 +2,46 @@ use std::{marker::PhantomData, mem::MaybeUninit};
 
 use crate::{
   deserialize::{
-    create_default_deserializer, DefaultDeserializable, DeserError, DeserResult, Deserializer,
+    create_default_deserializer, feed_split, AnyToken, DefaultDeserializable, DeserError,
+    DeserResult, Deserializer,
   },
-  stream_parser::{Token, TokenInfo},
+  stream_parser::{CoarseToken, Token, TokenInfo},
 };
 
 pub trait StructHelperReceiverTrait<Return> {
   fn start_value(&mut self, key: &str) -> Result<(), DeserError>;
   fn feed_value(&mut self, token: Token) -> Result<DeserResult<()>, DeserError>;
+  /** Feed a coarse token to the value, see `Deserializer::feed_coarse` */
+  fn feed_value_coarse(&mut self, token: CoarseToken) -> Result<DeserResult<()>, DeserError> {
+    feed_split(token, |token| self.feed_value(token))
+  }
   fn end(&mut self) -> Result<Return, DeserError>;
 }
 
+/** A token which can be fed to the value of `StructHelperReceiverTrait` */
+trait ValueToken: AnyToken {
+  fn feed_value_to<Return>(
+    self,
+    receiver: &mut impl StructHelperReceiverTrait<Return>,
+  ) -> Result<DeserResult<()>, DeserError>;
+}
+impl ValueToken for Token {
+  fn feed_value_to<Return>(
+    self,
+    receiver: &mut impl StructHelperReceiverTrait<Return>,
+  ) -> Result<DeserResult<()>, DeserError> {
+    receiver.feed_value(self)
+  }
+}
+impl ValueToken for CoarseToken<'_> {
+  fn feed_value_to<Return>(
+    self,
+    receiver: &mut impl StructHelperReceiverTrait<Return>,
+  ) -> Result<DeserResult<()>, DeserError> {
+    receiver.feed_value_coarse(self)
+  }
+}
+
 #[derive(Debug, Clone, Copy)]
 enum StageEnum {
   NotStarted,
@@ -34,13 +63,13 @@ pub struct StructHelperReceiverDeserializer<Return, Receiver: StructHelperReceiv
   stage: StageEnum,
   _phantom: PhantomData<Return>,
 }
-impl<Return, Receiver: StructHelperReceiverTrait<Return>> Deserializer<Return>
-  for StructHelperReceiverDeserializer<Return, Receiver>
+impl<Return, Receiver: StructHelperReceiverTrait<Return>>
+  StructHelperReceiverDeserializer<Return, Receiver>
 {
-  fn feed_token(&mut self, token: Token) -> Result<DeserResult<Return>, DeserError> {
+  fn feed<Tk: ValueToken>(&mut self, token: Tk) -> Result<DeserResult<Return>, DeserError> {
     match self.stage {
       StageEnum::Key => {
-        match unsafe { self.key_subreceiver.assume_init_mut() }.feed_token(token)? {
+        match token.feed_to(unsafe { self.key_subreceiver.assume_init_mut() })? {
           DeserResult::Complete(key) => {
             unsafe { self.key_subreceiver.assume_init_drop() };
             self.key.write(key);
@@ -57,7 +86,7 @@ impl<Return, Receiver: StructHelperReceiverTrait<Return>> Deserializer<Return>
         }
       }
       StageEnum::Value => {
-        match self.receiver.feed_value(token)? {
+        match token.feed_value_to(&mut self.receiver)? {
           DeserResult::Complete(_) => {
             self.stage = StageEnum::ValueEnd;
             return Ok(DeserResult::Continue);
@@ -73,7 +102,7 @@ impl<Return, Receiver: StructHelperReceiverTrait<Return>> Deserializer<Return>
     }
     if matches!(self.stage, StageEnum::WaitKey) {
       if !token.is_space() {
-        if matches!(token.info, TokenInfo::ObjectEnd) {
+        if matches!(token.get_info(), TokenInfo::ObjectEnd) {
           // trailing comma
           self.stage = StageEnum::End;
           return Ok(DeserResult::Complete(self.receiver.end()?));
@@ -82,7 +111,7 @@ impl<Return, Receiver: StructHelperReceiverTrait<Return>> Deserializer<Return>
         self.key_subreceiver.write(create_default_deserializer::<String>());
         self.stage = StageEnum::Key;
 
-        match unsafe { self.key_subreceiver.assume_init_mut() }.feed_token(token)? {
+        match token.feed_to(unsafe { self.key_subreceiver.assume_init_mut() })? {
           DeserResult::Complete(key) => {
             unsafe { self.key_subreceiver.assume_init_drop() };
             self.key.write(key);
@@ -98,7 +127,7 @@ impl<Return, Receiver: StructHelperReceiverTrait<Return>> Deserializer<Return>
       if !token.is_space() {
         self.stage = StageEnum::Value;
         self.receiver.start_value(unsafe { self.key.assume_init_ref() })?;
-        match self.receiver.feed_value(token)? {
+        match token.feed_value_to(&mut self.receiver)? {
           DeserResult::Complete(_) => self.stage = StageEnum::ValueEnd,
           DeserResult::CompleteWithRollback(_) => unreachable!(),
           DeserResult::Continue => {}
@@ -107,7 +136,7 @@ impl<Return, Receiver: StructHelperReceiverTrait<Return>> Deserializer<Return>
       return Ok(DeserResult::Continue);
     }
 
-    match token.info {
+    match token.get_info() {
       TokenInfo::ObjectStart => {
         assert!(matches!(self.stage, StageEnum::NotStarted));
         self.stage = StageEnum::WaitKey;
@@ -137,6 +166,16 @@ impl<Return, Receiver: StructHelperReceiverTrait<Return>> Deserializer<Return>
     }
   }
 }
+impl<Return, Receiver: StructHelperReceiverTrait<Return>> Deserializer<Return>
+  for StructHelperReceiverDeserializer<Return, Receiver>
+{
+  fn feed_token(&mut self, token: Token) -> Result<DeserResult<Return>, DeserError> {
+    self.feed(token)
+  }
+  fn feed_coarse(&mut self, token: CoarseToken) -> Result<DeserResult<Return>, DeserError> {
+    self.feed(token)
+  }
+}
 
 impl<Return, Receiver: StructHelperReceiverTrait<Return>> Drop
   for StructHelperReceiverDeserializer<Return, Receiver>
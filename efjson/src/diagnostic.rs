@@ -0,0 +1,260 @@
+use std::fmt::Write;
+
+#[cfg(feature = "deserialize")]
+use crate::deserialize::DeserError;
+#[cfg(feature = "event")]
+use crate::event_parser::{EmitterError, ParserError};
+use crate::stream_parser::{
+  is_next_line, ErrorKind, JsonPointer, LineIndex, ReaderError, StreamError,
+};
+use crate::unicode::{is_json5_whitespace, is_json_whitespace};
+
+/** How to render a `Diagnostic` */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DiagnosticStyle {
+  #[default]
+  Plain,
+  /** colored with ANSI escape sequences */
+  Ansi,
+}
+
+/**
+A human-readable report of an error, which can be rendered with the source text.
+
+# Example
+```rust
+use efjson::{diagnostic::{Diagnostic, DiagnosticStyle}, stream_parser::StreamParser, ParserOption};
+let source = "{\n  \"a\": [1, 2,]\n}";
+let err = StreamParser::parse(ParserOption::empty(), source).unwrap_err();
+let diagnostic = Diagnostic::from_stream_error(&err);
+assert_eq!(
+  diagnostic.render(source, DiagnosticStyle::Plain),
+  "error[CommaInEmptyArray]: empty array with trailing comma not allowed
+ --> 2:14
+  |
+2 |   \"a\": [1, 2,]
+  |              ^
+  = hint: trailing comma requires `ParserOption::TRAILING_COMMA_IN_ARRAY`
+"
+);
+```
+*/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+  /** `None` if the error is not reported by the stream parser */
+  pub kind: Option<ErrorKind>,
+  pub message: String,
+  pub hint: Option<&'static str>,
+  /** the offset in bytes of the source, `None` if unknown */
+  pub byte_position: Option<usize>,
+  /** the path of the value, see `StreamParser::current_path` */
+  pub path: Option<JsonPointer>,
+}
+
+impl Diagnostic {
+  pub fn from_stream_error(err: &StreamError) -> Self {
+    Self {
+      kind: Some(err.kind),
+      message: err.kind.stringify().to_string(),
+      hint: hint(err.kind, err.character),
+      byte_position: Some(err.byte_position),
+      path: None,
+    }
+  }
+  pub fn from_reader_error(err: &ReaderError) -> Self {
+    match err {
+      ReaderError::IoError(err) => {
+        Self { kind: None, message: err.to_string(), hint: None, byte_position: None, path: None }
+      }
+      ReaderError::StreamParserError(err) => Self::from_stream_error(err),
+    }
+  }
+  /**
+  `byte_position` is the offset of the character which caused the error,
+  that is, `EventParser::get_byte_position()` before the character is fed.
+  */
+  #[cfg(feature = "event")]
+  pub fn from_emitter_error(err: &EmitterError, byte_position: usize) -> Self {
+    let hint = match err {
+      EmitterError::TypeRejected(_) => Some("the receiver does not accept a value of this type"),
+      EmitterError::InvalidInteger | EmitterError::InvalidNumber => None,
+    };
+    Self {
+      kind: None,
+      message: err.to_string(),
+      hint,
+      byte_position: Some(byte_position),
+      path: None,
+    }
+  }
+  /** See `from_emitter_error` for `byte_position`, which is ignored for stream errors */
+  #[cfg(feature = "event")]
+  pub fn from_parser_error(err: &ParserError, byte_position: usize) -> Self {
+    match err {
+      ParserError::StreamParserError(err) => Self::from_stream_error(err),
+      ParserError::EmitterParserError(err) => Self::from_emitter_error(err, byte_position),
+    }
+  }
+  /**
+  Errors of `ReaderError` are located, other errors (for example, type mismatches of deserializers) have no position.
+
+  # Note
+  A `StreamError` is converted to its message when it is boxed into a `DeserError`,
+  use `from_stream_error` to locate it before the conversion.
+  */
+  #[cfg(feature = "deserialize")]
+  pub fn from_deser_error(err: &DeserError) -> Self {
+    if let Some(err) = err.downcast_ref::<ReaderError>() {
+      Self::from_reader_error(err)
+    } else {
+      Self { kind: None, message: err.to_string(), hint: None, byte_position: None, path: None }
+    }
+  }
+  /**
+  Attach the path of the value, for example:
+  `Diagnostic::from_stream_error(&err).with_path(parser.current_path())`.
+  */
+  pub fn with_path(mut self, path: Option<JsonPointer>) -> Self {
+    self.path = path;
+    self
+  }
+
+  /**
+  Render the diagnostic with the offending line of `source` and a caret under the character.
+
+  Line and column numbers start from 1 (columns count characters).
+  */
+  pub fn render(&self, source: &str, style: DiagnosticStyle) -> String {
+    let paint = |code: &'static str| if style == DiagnosticStyle::Ansi { code } else { "" };
+    let (red, blue, cyan, bold, reset) = (
+      paint("\x1b[1;31m"),
+      paint("\x1b[1;34m"),
+      paint("\x1b[1;36m"),
+      paint("\x1b[1m"),
+      paint("\x1b[0m"),
+    );
+
+    let mut out = String::new();
+    match self.kind {
+      Some(kind) => write!(out, "{red}error[{kind:?}]{reset}{bold}: {}{reset}", self.message),
+      None => write!(out, "{red}error{reset}{bold}: {}{reset}", self.message),
+    }
+    .unwrap();
+    out.push('\n');
+    if let Some(byte_position) = self.byte_position {
+      let index = LineIndex::new(source);
+      let position = index.position_of_byte(byte_position);
+      let (line, text, column) =
+        (position.line, index.get_line_text(position.line), position.column);
+      let number = (line + 1).to_string();
+      let pad = " ".repeat(number.len());
+      /* keep tabs, so that the caret is aligned */
+      let indent: String =
+        text.chars().take(column).map(|c| if c == '\t' { '\t' } else { ' ' }).collect();
+      writeln!(out, "{pad}{blue}-->{reset} {}:{}", line + 1, column + 1).unwrap();
+      writeln!(out, "{pad} {blue}|{reset}").unwrap();
+      writeln!(out, "{blue}{number} |{reset} {text}").unwrap();
+      writeln!(out, "{pad} {blue}|{reset} {indent}{red}^{reset}").unwrap();
+      if let Some(path) = self.path.as_ref() {
+        writeln!(out, "{pad} {blue}={reset} {cyan}path{reset}: {path}").unwrap();
+      }
+      if let Some(hint) = self.hint {
+        writeln!(out, "{pad} {blue}={reset} {cyan}hint{reset}: {hint}").unwrap();
+      }
+    } else {
+      if let Some(path) = self.path.as_ref() {
+        writeln!(out, "{cyan}path{reset}: {path}").unwrap();
+      }
+      if let Some(hint) = self.hint {
+        writeln!(out, "{cyan}hint{reset}: {hint}").unwrap();
+      }
+    }
+    out
+  }
+}
+
+/** The hint for extensions and common mistakes */
+fn hint(kind: ErrorKind, c: char) -> Option<&'static str> {
+  let number_hint = |c: char| match c {
+    'x' | 'X' => Some("hexadecimal integers require `ParserOption::HEXADECIMAL_INTEGER`"),
+    'o' | 'O' => Some("octal integers require `ParserOption::OCTAL_INTEGER`"),
+    'b' | 'B' => Some("binary integers require `ParserOption::BINARY_INTEGER`"),
+    'N' => Some("`NaN` requires `ParserOption::NAN`"),
+    'I' => Some("`Infinity` requires `ParserOption::INFINITY`"),
+    '+' => Some("positive sign requires `ParserOption::POSITIVE_SIGN`"),
+    '.' => Some("empty integer part requires `ParserOption::EMPTY_INTEGER`"),
+    '\'' => Some("single-quoted strings require `ParserOption::SINGLE_QUOTE`"),
+    '/' => Some(
+      "comments require `ParserOption::SINGLE_LINE_COMMENT` or `ParserOption::MULTI_LINE_COMMENT`",
+    ),
+    c if is_json5_whitespace(c) && !is_json_whitespace(c) => {
+      Some("this whitespace requires `ParserOption::JSON5_WHITESPACE`")
+    }
+    _ => None,
+  };
+  match kind {
+    ErrorKind::TooManyRecursions => Some("the nesting is deeper than `Limits::max_depth`"),
+    ErrorKind::InputTooLong => Some("the input is longer than `Limits::max_input_length`"),
+    ErrorKind::StringTooLong => Some("the string is longer than `Limits::max_string_length`"),
+    ErrorKind::NumberTooLong => Some("the number is longer than `Limits::max_number_length`"),
+    ErrorKind::TooManyElements => {
+      Some("the container exceeds `Limits::max_array_length` or `Limits::max_object_length`")
+    }
+    ErrorKind::TooManyNodes => Some("the document has more values than `Limits::max_nodes`"),
+    ErrorKind::DuplicateKey => Some("I-JSON forbids duplicate keys in an object"),
+    ErrorKind::NumberOutOfRange => {
+      Some("I-JSON numbers must be representable as IEEE 754 double precision numbers")
+    }
+    ErrorKind::NoncharacterForbidden => Some("I-JSON forbids Unicode noncharacters in strings"),
+    ErrorKind::InvalidInputUtf => Some("the input is not valid Unicode text"),
+    ErrorKind::MultipleTokens => {
+      Some("a character may produce two tokens with the option, use `StreamParser::feed_one_into`")
+    }
+    ErrorKind::Eof => Some("the input ends before the JSON value is complete"),
+    ErrorKind::NonwhitespaceAfterEnd => Some("only one JSON value is allowed"),
+    ErrorKind::TrailingCommaForbidden => Some("remove the extra comma"),
+    ErrorKind::Unexpected | ErrorKind::UnexpectedInNumber => number_hint(c),
+    ErrorKind::WrongBracket => Some("the closing bracket does not match the opening one"),
+    ErrorKind::WrongColon => Some("a colon is only allowed after a property name"),
+    ErrorKind::EmptyValue => Some("empty input requires `ParserOption::ALLOW_EMPTY_VALUE`"),
+    ErrorKind::InvalidEscapedUtf | ErrorKind::IncompleteSurrogatePair => {
+      Some("lone surrogates in escapes require `ParserOption::LONE_SURROGATE`")
+    }
+    ErrorKind::CommaInEmptyArray if c == ']' => {
+      Some("trailing comma requires `ParserOption::TRAILING_COMMA_IN_ARRAY`")
+    }
+    ErrorKind::CommaInEmptyObject if c == '}' => {
+      Some("trailing comma requires `ParserOption::TRAILING_COMMA_IN_OBJECT`")
+    }
+    ErrorKind::CommaInEmptyArray | ErrorKind::CommaInEmptyObject => Some("remove the extra comma"),
+    ErrorKind::BadPropertyNameInObject if c == '\\' || c == '_' || c == '$' || c.is_alphabetic() => {
+      Some("unquoted property names require `ParserOption::IDENTIFIER_KEY`")
+    }
+    ErrorKind::BadPropertyNameInObject => Some("property names must be strings"),
+    ErrorKind::ExpectedColon => Some("add a colon after the property name"),
+    ErrorKind::RepeatedColon => Some("remove the extra colon"),
+    ErrorKind::BadEscapeInString if is_next_line(c) => {
+      Some("escaped line breaks require `ParserOption::MULTILINE_STRING`")
+    }
+    ErrorKind::BadEscapeInString if matches!(c, 'x' | 'v' | '0' | '\'') => {
+      Some("JSON5 escape sequences require `ParserOption::JSON5_STRING_ESCAPE`")
+    }
+    ErrorKind::BadEscapeInString => {
+      Some("valid escape sequences are `\\\"`, `\\\\`, `\\/`, `\\b`, `\\f`, `\\n`, `\\r`, `\\t` and `\\uXXXX`")
+    }
+    ErrorKind::BadHexEscapeInString => Some("`\\x` must be followed by two hexadecimal digits"),
+    ErrorKind::BadUnicodeEscapeInString => Some("`\\u` must be followed by four hexadecimal digits"),
+    ErrorKind::ControlCharacterForbiddenInString => Some("control characters must be escaped"),
+    ErrorKind::SingleQuoteForbidden => {
+      Some("single-quoted strings require `ParserOption::SINGLE_QUOTE`")
+    }
+    ErrorKind::EmptyFractionPart => Some("empty fraction part requires `ParserOption::EMPTY_FRACTION`"),
+    ErrorKind::EmptyIntegerPart if matches!(c, '.' | 'N' | 'I') => number_hint(c),
+    ErrorKind::LeadingZeroForbidden => Some("leading zeros are not allowed in decimal numbers"),
+    ErrorKind::PositiveSignForbidden => number_hint('+'),
+    ErrorKind::LoneDecimalPoint => Some("a number needs at least one digit"),
+    ErrorKind::CommentForbidden => number_hint('/'),
+    ErrorKind::CommentNotClosed => Some("close the comment with `*/`"),
+    _ => None,
+  }
+}
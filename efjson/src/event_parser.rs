@@ -1,9 +1,13 @@
 use std::hint::unreachable_unchecked;
 
-use crate::stream_parser::{self, Category, Location, Stage, StreamParser, Token, TokenInfo};
+use crate::stream_parser::{
+  self, Category, Checkpoint, JsonPointer, Limits, Location, RepairWarning, Stage, StreamParser,
+  Token, TokenInfo, TokenSink,
+};
 use crate::{JsonArray, JsonObject, JsonValue, ParserOption};
 
 #[derive(Default)]
+#[allow(clippy::type_complexity)]
 pub struct EventObjectReceiver {
   pub set: Option<Box<dyn FnMut(&str, &JsonValue)>>,
   pub next: Option<Box<dyn FnMut()>>,
@@ -12,12 +16,14 @@ pub struct EventObjectReceiver {
   pub subreceiver: Option<Box<dyn FnMut(&str) -> Option<EventReceiver>>>,
 }
 #[derive(Default)]
+#[allow(clippy::type_complexity)]
 pub struct EventArrayReceiver {
   pub set: Option<Box<dyn FnMut(usize, &JsonValue)>>,
   pub next: Option<Box<dyn FnMut(usize)>>,
   pub subreceiver: Option<Box<dyn FnMut(usize) -> Option<EventReceiver>>>,
 }
 
+#[allow(clippy::type_complexity)]
 pub struct EventReceiver {
   pub start: Option<Box<dyn FnOnce()>>,
   pub end: Option<Box<dyn FnOnce()>>,
@@ -110,11 +116,11 @@ struct _State {
   substate: _SubState,
 }
 
-fn parse_number(s: &Vec<char>) -> Result<f64, EmitterError> {
+fn parse_number(s: &[char]) -> Result<f64, EmitterError> {
   let s: String = s.iter().collect();
   s.parse::<f64>().map_err(|_| EmitterError::InvalidNumber)
 }
-fn parse_integer(s: &Vec<char>) -> Option<i64> {
+fn parse_integer(s: &[char]) -> Option<i64> {
   let c0 = unsafe { *s.get_unchecked(0) };
   let start = (c0 == '+' || c0 == '-') as usize;
   let radix: u64 = if unsafe { *s.get_unchecked(start) } == '0' {
@@ -213,13 +219,13 @@ impl EventEmitter {
 
   pub fn _need_save(&self) -> bool {
     let len = self.stack.len();
-    return unsafe { self.stack.get_unchecked(len - 1) }.receiver.save.is_some()
+    unsafe { self.stack.get_unchecked(len - 1) }.receiver.save.is_some()
       || (len >= 2
         && match &unsafe { self.stack.get_unchecked(len - 2) }.substate {
           _SubState::Array(state) => state.save_child,
           _SubState::Object(state) => state.save_child,
           _ => false,
-        });
+        })
   }
   pub fn _feed_stateless(&mut self, token: Token) -> Result<(), EmitterError> {
     let state = unsafe { self.stack.last_mut().unwrap_unchecked() };
@@ -317,7 +323,9 @@ impl EventEmitter {
       }
       TokenInfo::StringNormal => {
         call_opt!(state.receiver.string_append, token.c);
-        list.as_mut().map(|l| l.push(token.c));
+        if let Some(l) = list.as_mut() {
+          l.push(token.c);
+        }
       }
       TokenInfo::StringEscapeStart
       | TokenInfo::StringEscapeUnicodeStart
@@ -325,12 +333,23 @@ impl EventEmitter {
       | TokenInfo::StringNextLine => {}
       TokenInfo::StringEscape(c) => {
         call_opt!(state.receiver.string_append, c);
-        list.as_mut().map(|l| l.push(token.c));
+        if let Some(l) = list.as_mut() {
+          l.push(token.c);
+        }
       }
       TokenInfo::StringEscapeUnicode(_, c) | TokenInfo::StringEscapeHex(_, c) => {
         if let Some(c) = c {
           call_opt!(state.receiver.string_append, c);
-          list.as_mut().map(|l| l.push(c));
+          if let Some(l) = list.as_mut() {
+            l.push(c);
+          }
+        }
+      }
+      /* a lone surrogate is replaced with U+FFFD */
+      TokenInfo::StringEscapeSurrogate(_) => {
+        call_opt!(state.receiver.string_append, char::REPLACEMENT_CHARACTER);
+        if let Some(l) = list.as_mut() {
+          l.push(char::REPLACEMENT_CHARACTER);
         }
       }
       _ => unreachable!(),
@@ -458,7 +477,7 @@ impl EventEmitter {
           .object
           .subreceiver
           .as_mut()
-          .and_then(|f| f(&obj.key.as_ref().unwrap()))
+          .and_then(|f| f(obj.key.as_ref().unwrap()))
           .unwrap_or_else(EventReceiver::new_all);
         self.stack.push(_State { receiver: next_receiver, substate: _SubState::None })
       }
@@ -547,7 +566,7 @@ impl EventEmitter {
         if let Some(list) = list {
           // saved
           if state.receiver.accept_integer {
-            if let Some(int_val) = parse_integer(&list) {
+            if let Some(int_val) = parse_integer(list) {
               call_opt_once!(state.receiver.end);
               call_opt_once!(state.receiver.integer_save, int_val);
             } else if !state.receiver.accept_number {
@@ -555,7 +574,7 @@ impl EventEmitter {
             }
           }
 
-          let val = parse_number(&list)?;
+          let val = parse_number(list)?;
           self._end_value(val);
         } else {
           self._end_value_nosave();
@@ -606,6 +625,37 @@ impl EventEmitter {
     Ok(())
   }
 }
+/**
+Feed the tokens of `StreamParser::feed_to` to the emitter, which breaks with the error (if any).
+
+# Example
+```rust
+use efjson::{event_parser::{EventEmitter, EventReceiver}, stream_parser::StreamParser};
+use efjson::{JsonValue, ParserOption};
+use std::{cell::RefCell, rc::Rc};
+let length = Rc::new(RefCell::new(0));
+let mut receiver = EventReceiver::new_all();
+let length2 = length.clone();
+receiver.save = Some(Box::new(move |value| {
+  if let JsonValue::Array(array) = value {
+    *length2.borrow_mut() = array.len();
+  }
+}));
+let mut emitter = EventEmitter::new(receiver);
+let mut parser = StreamParser::new(ParserOption::empty());
+assert!(parser.feed_to("[1, 2.5, \"x\"]\0", &mut emitter).unwrap().is_continue());
+assert_eq!(*length.borrow(), 3);
+assert!(parser.feed_to("\0", &mut emitter).is_err());
+```
+*/
+impl TokenSink<EmitterError> for EventEmitter {
+  fn token(&mut self, token: Token) -> std::ops::ControlFlow<EmitterError> {
+    match self.feed_one(token) {
+      Ok(()) => std::ops::ControlFlow::Continue(()),
+      Err(err) => std::ops::ControlFlow::Break(err),
+    }
+  }
+}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ParserError {
@@ -621,20 +671,43 @@ impl std::fmt::Display for ParserError {
   }
 }
 
+/** A state of `EventParser` to roll back to, see `EventParser::checkpoint` */
+#[derive(Debug, Clone)]
+pub struct EventCheckpoint {
+  checkpoint: Checkpoint,
+  /** the number of pending tokens */
+  pending: usize,
+  /** the number of commits before the checkpoint */
+  commits: usize,
+}
+
 pub struct EventParser {
   emitter: EventEmitter,
   parser: StreamParser,
+  /** the tokens fed after a checkpoint, which are not yet passed to the emitter */
+  pending: Option<Vec<Token>>,
+  commits: usize,
 }
 impl EventParser {
   pub fn new(receiver: EventReceiver, option: ParserOption) -> Self {
-    Self { emitter: EventEmitter::new(receiver), parser: StreamParser::new(option) }
+    Self::from_parser(receiver, StreamParser::new(option))
+  }
+  /** Create a parser with limits, see `StreamParser::with_limits` */
+  pub fn with_limits(receiver: EventReceiver, option: ParserOption, limits: Limits) -> Self {
+    Self::from_parser(receiver, StreamParser::with_limits(option, limits))
   }
+  fn from_parser(receiver: EventReceiver, parser: StreamParser) -> Self {
+    Self { emitter: EventEmitter::new(receiver), parser, pending: None, commits: 0 }
+  }
+  /** Feed a character, a comma may be inserted before it (see `StreamParser::feed_one_into`) */
   pub fn feed_one(&mut self, c: char) -> Result<(), ParserError> {
-    match self.parser.feed_one(c) {
-      Ok(token) => match self.emitter.feed_one(token) {
-        Ok(_) => Ok(()),
-        Err(e) => Err(ParserError::EmitterParserError(e)),
-      },
+    match self.parser.feed_pair(c) {
+      Ok((comma, token)) => {
+        if let Some(comma) = comma {
+          self.emit(comma)?;
+        }
+        self.emit(token)
+      }
       Err(err) => Err(ParserError::StreamParserError(err)),
     }
   }
@@ -644,9 +717,92 @@ impl EventParser {
     }
     Ok(())
   }
+  /**
+  Feed a chunk of UTF-8 input, a code point may be split across chunks.
+
+  See `StreamParser::feed_byte`.
+  */
+  pub fn feed_bytes(&mut self, bytes: &[u8]) -> Result<(), ParserError> {
+    for &b in bytes {
+      match self.parser.decode_byte(b) {
+        Ok(Some(c)) => self.feed_one(c)?,
+        Ok(None) => {}
+        Err(err) => return Err(ParserError::StreamParserError(err)),
+      }
+    }
+    Ok(())
+  }
   pub fn end(&mut self) -> Result<(), ParserError> {
     self.feed_one('\0')
   }
+  fn emit(&mut self, token: Token) -> Result<(), ParserError> {
+    match self.pending.as_mut() {
+      Some(pending) => {
+        pending.push(token);
+        Ok(())
+      }
+      None => self.emitter.feed_one(token).map_err(ParserError::EmitterParserError),
+    }
+  }
+}
+impl EventParser {
+  /**
+  Take a checkpoint, see `StreamParser::checkpoint`.
+
+  Since the callbacks of receivers cannot be undone,
+  the tokens fed after a checkpoint are kept pending,
+  and they are passed to the emitter only when `commit` is called.
+
+  # Example
+  ```rust
+  use efjson::{event_parser::{EventParser, EventReceiver}, ParserOption};
+  use std::{cell::RefCell, rc::Rc};
+  let keys = Rc::new(RefCell::new(String::new()));
+  let mut receiver = EventReceiver::new_all();
+  let keys2 = keys.clone();
+  receiver.object.set = Some(Box::new(move |key, _| keys2.borrow_mut().push_str(key)));
+
+  let mut parser = EventParser::new(receiver, ParserOption::empty());
+  parser.feed("{\"a\": 1, ").unwrap();
+  let checkpoint = parser.checkpoint();
+  parser.feed("\"b\": true, ").unwrap();
+  parser.rollback(&checkpoint);
+  parser.feed("\"c\": 2}").unwrap();
+  assert_eq!(*keys.borrow(), "a");
+  parser.commit().unwrap();
+  assert_eq!(*keys.borrow(), "ac");
+  ```
+  */
+  pub fn checkpoint(&mut self) -> EventCheckpoint {
+    let pending = self.pending.get_or_insert_with(Vec::new).len();
+    EventCheckpoint { checkpoint: self.parser.checkpoint(), pending, commits: self.commits }
+  }
+  /**
+  Restore the state saved by `checkpoint`, the pending tokens after it are dropped.
+
+  # Panics
+  Panics if `commit` is called after the checkpoint is taken.
+  */
+  pub fn rollback(&mut self, checkpoint: &EventCheckpoint) {
+    assert_eq!(checkpoint.commits, self.commits, "the checkpoint has been committed");
+    self.parser.rollback(&checkpoint.checkpoint);
+    if let Some(pending) = self.pending.as_mut() {
+      pending.truncate(checkpoint.pending);
+    }
+  }
+  /**
+  Pass the pending tokens to the emitter, the checkpoints taken before cannot be rolled back to.
+
+  # Errors
+  Returns the first error of the emitter.
+  */
+  pub fn commit(&mut self) -> Result<(), ParserError> {
+    self.commits += 1;
+    match self.pending.take() {
+      Some(pending) => self.emitter.feed(pending).map_err(ParserError::EmitterParserError),
+      None => Ok(()),
+    }
+  }
 }
 impl EventParser {
   pub fn get_position(&self) -> usize {
@@ -658,12 +814,38 @@ impl EventParser {
   pub fn get_column(&self) -> usize {
     self.parser.get_column()
   }
+  pub fn get_byte_position(&self) -> usize {
+    self.parser.get_byte_position()
+  }
   pub fn get_location(&self) -> Location {
     self.parser.get_location()
   }
   pub fn get_stage(&self) -> Stage {
     self.parser.get_stage()
   }
+  pub fn get_depth(&self) -> usize {
+    self.parser.get_depth()
+  }
+  /** See `StreamParser::enable_path_tracking` */
+  pub fn enable_path_tracking(&mut self) {
+    self.parser.enable_path_tracking()
+  }
+  /** See `StreamParser::current_path` */
+  pub fn current_path(&self) -> Option<JsonPointer> {
+    self.parser.current_path()
+  }
+  /** See `StreamParser::enable_ijson` */
+  pub fn enable_ijson(&mut self) {
+    self.parser.enable_ijson()
+  }
+  /** See `StreamParser::get_warnings` */
+  pub fn get_warnings(&self) -> &[RepairWarning] {
+    self.parser.get_warnings()
+  }
+  /** See `StreamParser::take_warnings` */
+  pub fn take_warnings(&mut self) -> Vec<RepairWarning> {
+    self.parser.take_warnings()
+  }
 }
 impl EventParser {
   pub fn parse(
@@ -677,4 +859,13 @@ impl EventParser {
     }
     parser.end()
   }
+  pub fn parse_bytes(
+    receiver: EventReceiver,
+    option: ParserOption,
+    bytes: &[u8],
+  ) -> Result<(), ParserError> {
+    let mut parser = EventParser::new(receiver, option);
+    parser.feed_bytes(bytes)?;
+    parser.end()
+  }
 }
@@ -6,6 +6,10 @@ pub mod deserialize;
 #[cfg(feature = "event")]
 pub mod event_parser;
 pub mod stream_parser;
+pub use stream_parser::validate;
+
+pub mod diagnostic;
+pub mod unicode;
 
 #[cfg(feature = "derive")]
 pub use efjson_derive::*;
@@ -0,0 +1,174 @@
+use std::sync::Arc;
+
+use super::outer::{self, *};
+use super::{IJsonChecker, PathTracker, StreamParser};
+
+/**
+A state of `StreamParser` to roll back to, see `StreamParser::checkpoint`.
+
+A checkpoint can be rolled back to any number of times, and it can be cloned cheaply.
+*/
+#[derive(Debug, Clone)]
+pub struct Checkpoint {
+  raw: RawSnapshot,
+  utf8: Utf8Decoder,
+  byte_position: usize,
+  astral: usize,
+  astral_line: usize,
+  /** the bottom of the stack, at least `raw.len` bits (`None` if the stack is empty) */
+  stack: Option<Arc<[u8]>>,
+  /** the counters of `Limits` */
+  counters: Option<(usize, usize, Vec<usize>)>,
+  path: Option<Arc<PathTracker>>,
+  ijson: Option<Arc<IJsonChecker>>,
+  /** the number of warnings, see `StreamParser::get_warnings` */
+  warnings: usize,
+}
+impl Checkpoint {
+  /** Get the position where the checkpoint is taken */
+  pub fn get_position(&self) -> usize {
+    self.raw.position
+  }
+  /** Get the depth of nesting where the checkpoint is taken */
+  pub fn get_depth(&self) -> usize {
+    self.raw.len as usize
+  }
+}
+
+impl StreamParser {
+  /**
+  Take a checkpoint of the current state, which can be restored by `rollback`.
+
+  The stack is copied on write: checkpoints share a copy of it,
+  until the parser leaves the containers open at the checkpoint.
+  So taking a checkpoint costs `O(1)` in most cases,
+  and `O(depth)` otherwise (or if the parser has `Limits`).
+
+  The tracked path and the state of the I-JSON checks are shared in the same way:
+  the first token fed after a checkpoint copies them except the keys of each object,
+  and the keys of an object are copied when a key is added to it.
+
+  # Example
+  ```rust
+  use efjson::{stream_parser::StreamParser, ParserOption};
+  let mut parser = StreamParser::new(ParserOption::empty());
+  parser.feed("[1, {\"a\": 2").unwrap();
+  let checkpoint = parser.checkpoint();
+  /* what if the input ended here? */
+  assert!(parser.feed_one('\0').is_err());
+  parser.rollback(&checkpoint);
+  assert!(parser.feed("}]\0").is_ok());
+
+  parser.rollback(&checkpoint);
+  parser.feed(", \"b\": [3]}]").unwrap();
+  assert_eq!(parser.get_position(), 23);
+  ```
+  */
+  pub fn checkpoint(&mut self) -> Checkpoint {
+    let len = self.raw.len;
+    if self.image_len < len {
+      self.image = Some(Arc::from(self.raw.get_stack()));
+      self.image_len = len;
+    }
+    Checkpoint {
+      raw: self.raw.snapshot(),
+      utf8: self.utf8,
+      byte_position: self.byte_position,
+      astral: self.astral,
+      astral_line: self.astral_line,
+      stack: if len == 0 { None } else { self.image.clone() },
+      counters: self.limits.as_ref().map(|limiter| {
+        let (length, nodes, counts) = limiter.get_counters();
+        (length, nodes, counts.to_vec())
+      }),
+      path: self.path.clone(),
+      ijson: self.ijson.clone(),
+      warnings: self.warnings.len(),
+    }
+  }
+  /**
+  Restore the state saved by `checkpoint`, including the position.
+
+  The limits, path tracking and I-JSON checks of this parser are kept, see `restore_state`.
+  */
+  pub fn rollback(&mut self, checkpoint: &Checkpoint) {
+    let len = checkpoint.raw.len;
+    if let Some(stack) = checkpoint.stack.as_ref() {
+      let unchanged =
+        self.image_len >= len && self.image.as_ref().is_some_and(|image| Arc::ptr_eq(image, stack));
+      if !unchanged {
+        let size = (len as usize).div_ceil(8);
+        self.reserve_stack(size);
+        self.raw.get_stack_mut(size).copy_from_slice(&stack[..size]);
+      }
+      self.image = Some(stack.clone());
+    }
+    self.image_len = len;
+    self.raw.restore(&checkpoint.raw);
+    self.utf8 = checkpoint.utf8;
+    self.byte_position = checkpoint.byte_position;
+    self.astral = checkpoint.astral;
+    self.astral_line = checkpoint.astral_line;
+    self.warnings.truncate(checkpoint.warnings);
+    if let Some(limiter) = self.limits.as_mut() {
+      let (length, nodes, counts) =
+        checkpoint.counters.clone().unwrap_or_else(|| (0, 0, vec![0; len as usize]));
+      limiter.set_counters(length, nodes, counts);
+    }
+    if self.path.is_some() {
+      /* the path is unknown if it is not tracked at the checkpoint */
+      self.path = Some(checkpoint.path.clone().unwrap_or_default());
+    }
+    if self.ijson.is_some() {
+      self.ijson = Some(
+        checkpoint
+          .ijson
+          .clone()
+          .unwrap_or_else(|| Arc::new(IJsonChecker::with_depth(len as usize))),
+      );
+    }
+  }
+
+  /** Update `image_len` after the tokens fed (in a block) from the depth `depth` */
+  pub(super) fn track_image(&mut self, mut depth: StackLength, ctokens: &[outer::Token]) {
+    if self.image_len == 0 {
+      return;
+    }
+    let mut low = depth;
+    for ctoken in ctokens {
+      match ctoken.r#type {
+        0x70 | 0x80 => depth += 1,
+        0x73 | 0x82 => {
+          depth -= 1;
+          low = low.min(depth);
+        }
+        _ => {}
+      }
+    }
+    self.image_len = self.image_len.min(low);
+  }
+  /** Enlarge the stack to at least `size` bytes */
+  #[cfg(not(feature = "fixed-stack"))]
+  pub(super) fn reserve_stack(&mut self, size: usize) {
+    if size <= self.raw.get_capacity() {
+      return;
+    }
+    let stack = unsafe { malloc(size) } as *mut u8;
+    if stack.is_null() {
+      std::alloc::handle_alloc_error(std::alloc::Layout::array::<u8>(size).unwrap());
+    }
+    unsafe {
+      if !self.raw.stack.is_null() {
+        std::ptr::copy_nonoverlapping(self.raw.stack, stack, self.raw.cap as usize);
+      }
+      free(self.raw.stack as *mut std::ffi::c_void);
+    }
+    self.raw.stack = stack;
+    self.raw.cap = size as StackLength;
+  }
+  /** The stack cannot be enlarged, `size` must not exceed `FIXED_STACK` */
+  #[cfg(feature = "fixed-stack")]
+  pub(super) fn reserve_stack(&mut self, size: usize) {
+    assert!(size <= FIXED_STACK, "the stack is too small");
+  }
+}
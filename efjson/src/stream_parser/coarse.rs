@@ -0,0 +1,272 @@
+use super::outer::*;
+use super::{Category, StreamError, StreamParser, Token, TokenInfo};
+
+/**
+A token of `StreamParser::feed_coarse`, where runs of characters are coalesced into slices of the input.
+
+The runs never contain escapes or structural characters, and the first character of a comment
+(and the one after it) is always a single token.
+A run may be split into several ones across calls, so may a string or a number.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoarseToken<'a> {
+  /** a token which is not in a run, including virtual commas (see `feed_one_into`) */
+  Single(Token),
+  /** unescaped characters of a string (`TokenInfo::StringNormal`) */
+  String(&'a str),
+  /** whitespace (`TokenInfo::Whitespace`) */
+  Whitespace(&'a str),
+  /** characters of a comment, and the token of the first one */
+  Comment(&'a str, TokenInfo),
+  /** characters of a number, and the token of the first one */
+  Number(&'a str, TokenInfo),
+}
+
+/** Get the token of a character in a run of numbers or comments from the one before */
+fn next_info(prev: TokenInfo, prev_c: char, c: char) -> TokenInfo {
+  match (prev, c) {
+    (TokenInfo::NumberIntegerSign, 'I') => TokenInfo::NumberInfinity(0, false),
+    (TokenInfo::NumberIntegerSign, 'N') => TokenInfo::NumberNan(0, false),
+    (TokenInfo::NumberIntegerSign | TokenInfo::NumberIntegerDigit, '0'..='9') => {
+      TokenInfo::NumberIntegerDigit
+    }
+    (TokenInfo::NumberIntegerSign | TokenInfo::NumberIntegerDigit, '.') => {
+      TokenInfo::NumberFractionStart
+    }
+    (TokenInfo::NumberIntegerDigit, 'x' | 'X') => TokenInfo::NumberHexStart,
+    (TokenInfo::NumberIntegerDigit, 'o' | 'O') => TokenInfo::NumberOctStart,
+    (TokenInfo::NumberIntegerDigit, 'b' | 'B') => TokenInfo::NumberBinStart,
+    (
+      TokenInfo::NumberIntegerDigit
+      | TokenInfo::NumberFractionStart
+      | TokenInfo::NumberFractionDigit,
+      'e' | 'E',
+    ) => TokenInfo::NumberExponentStart,
+    (TokenInfo::NumberFractionStart | TokenInfo::NumberFractionDigit, _) => {
+      TokenInfo::NumberFractionDigit
+    }
+    (TokenInfo::NumberExponentStart, '+' | '-') => TokenInfo::NumberExponentSign,
+    (TokenInfo::NumberExponentStart | TokenInfo::NumberExponentSign, _)
+    | (TokenInfo::NumberExponentDigit, _) => TokenInfo::NumberExponentDigit,
+    (TokenInfo::NumberHexStart | TokenInfo::NumberHex, _) => TokenInfo::NumberHex,
+    (TokenInfo::NumberOctStart | TokenInfo::NumberOct, _) => TokenInfo::NumberOct,
+    (TokenInfo::NumberBinStart | TokenInfo::NumberBin, _) => TokenInfo::NumberBin,
+    (TokenInfo::NumberNan(i, _), _) => TokenInfo::NumberNan(i + 1, i + 1 == 2),
+    (TokenInfo::NumberInfinity(i, _), _) => TokenInfo::NumberInfinity(i + 1, i + 1 == 7),
+    /* the opening `*` is never in a run */
+    (TokenInfo::CommentMultiLine, '/') if prev_c == '*' => TokenInfo::CommentMultiLineEnd,
+    (prev, _) => prev,
+  }
+}
+
+impl<'a> CoarseToken<'a> {
+  /** Whether the token is whitespace or a comment (see `Token::is_space`) */
+  pub fn is_space(&self) -> bool {
+    match self {
+      CoarseToken::Single(token) => token.is_space(),
+      CoarseToken::Whitespace(_) | CoarseToken::Comment(..) => true,
+      CoarseToken::String(_) | CoarseToken::Number(..) => false,
+    }
+  }
+  /** Get the text of the run, `None` for single tokens */
+  pub fn get_text(&self) -> Option<&'a str> {
+    match *self {
+      CoarseToken::Single(_) => None,
+      CoarseToken::String(text)
+      | CoarseToken::Whitespace(text)
+      | CoarseToken::Comment(text, _)
+      | CoarseToken::Number(text, _) => Some(text),
+    }
+  }
+  /**
+  Split the token into the tokens `feed` would produce.
+
+  # Example
+  ```rust
+  use efjson::{stream_parser::{CoarseToken, StreamParser}, ParserOption};
+  let input = "[\"ab\\n\", -1.5e3, /* note */ 0x1F]";
+  let option = ParserOption::make_json5();
+  let coarse = StreamParser::new(option).feed_coarse(input).unwrap();
+  assert!(coarse.contains(&CoarseToken::String("ab")));
+  let tokens: Vec<_> = coarse.iter().flat_map(|token| token.tokens()).collect();
+  assert_eq!(tokens, StreamParser::new(option).feed(input).unwrap());
+  ```
+  */
+  pub fn tokens(&self) -> CoarseTokens<'a> {
+    let (text, first) = match *self {
+      CoarseToken::Single(token) => {
+        return CoarseTokens {
+          single: Some(token),
+          chars: "".chars(),
+          first: token.info,
+          prev: None,
+        }
+      }
+      CoarseToken::String(text) => (text, TokenInfo::StringNormal),
+      CoarseToken::Whitespace(text) => (text, TokenInfo::Whitespace),
+      CoarseToken::Comment(text, first) | CoarseToken::Number(text, first) => (text, first),
+    };
+    CoarseTokens { single: None, chars: text.chars(), first, prev: None }
+  }
+}
+
+/** The iterator of `CoarseToken::tokens` */
+#[derive(Debug, Clone)]
+pub struct CoarseTokens<'a> {
+  single: Option<Token>,
+  chars: std::str::Chars<'a>,
+  /** the token of the first character */
+  first: TokenInfo,
+  /** the last token returned */
+  prev: Option<Token>,
+}
+impl Iterator for CoarseTokens<'_> {
+  type Item = Token;
+  fn next(&mut self) -> Option<Token> {
+    if let Some(token) = self.single.take() {
+      return Some(token);
+    }
+    let c = self.chars.next()?;
+    let info = match self.prev {
+      Some(prev) => next_info(prev.info, prev.c, c),
+      None => self.first,
+    };
+    self.prev = Some(Token { c, info });
+    self.prev
+  }
+}
+
+/** Whether a token of `info` continues the run `last` */
+fn is_same_run(last: &CoarseToken, info: TokenInfo) -> bool {
+  match last {
+    CoarseToken::String(_) => info == TokenInfo::StringNormal,
+    CoarseToken::Whitespace(_) => info == TokenInfo::Whitespace,
+    CoarseToken::Comment(..) => {
+      info.get_category() == Category::Comment && info != TokenInfo::CommentMayStart
+    }
+    CoarseToken::Number(..) => info.get_category() == Category::Number,
+    CoarseToken::Single(_) => false,
+  }
+}
+/** Append the run `s[offset..end]` whose first character has the token `info` to `dest` */
+fn push_run<'a>(
+  dest: &mut Vec<CoarseToken<'a>>,
+  run: &mut Option<usize>,
+  s: &'a str,
+  offset: usize,
+  end: usize,
+  info: TokenInfo,
+) {
+  if let (Some(last), Some(start)) = (dest.last_mut(), *run) {
+    if is_same_run(last, info) {
+      let text = &s[start..end];
+      *last = match *last {
+        CoarseToken::String(_) => CoarseToken::String(text),
+        CoarseToken::Whitespace(_) => CoarseToken::Whitespace(text),
+        CoarseToken::Comment(_, first) => CoarseToken::Comment(text, first),
+        CoarseToken::Number(_, first) => CoarseToken::Number(text, first),
+        CoarseToken::Single(_) => unreachable!(),
+      };
+      return;
+    }
+  }
+  let text = &s[offset..end];
+  dest.push(match info {
+    TokenInfo::StringNormal => CoarseToken::String(text),
+    TokenInfo::Whitespace => CoarseToken::Whitespace(text),
+    info if info.get_category() == Category::Number => CoarseToken::Number(text, info),
+    info => CoarseToken::Comment(text, info),
+  });
+  *run = Some(offset);
+}
+/**
+Append the token of the character at `offset` to `dest`,
+`opener` is whether the character follows the `/` starting a comment.
+*/
+fn push_token<'a>(
+  dest: &mut Vec<CoarseToken<'a>>,
+  run: &mut Option<usize>,
+  s: &'a str,
+  offset: usize,
+  token: Token,
+  opener: bool,
+) {
+  let in_run = match token.info.get_category() {
+    Category::Whitespace | Category::Number => true,
+    Category::String => token.info == TokenInfo::StringNormal,
+    /* the start of a comment is a single token, so that a run never contains the opening `*` */
+    Category::Comment => token.info != TokenInfo::CommentMayStart && !opener,
+    _ => false,
+  };
+  if in_run {
+    push_run(dest, run, s, offset, offset + token.c.len_utf8(), token.info);
+  } else {
+    *run = None;
+    dest.push(CoarseToken::Single(token));
+  }
+}
+
+impl StreamParser {
+  /**
+  Feed a string to the parser and return coarse tokens, where the runs of string content,
+  whitespace, comments and numbers are slices of `s` instead of one token per character.
+
+  Runs of string content and whitespace are skipped in bulk as `skip` does
+  (unless the tokens are checked or tracked, see `skip`).
+  See `CoarseToken::tokens` to get the tokens `feed` would produce.
+
+  # Note
+  If the string is ended, you need to explicitly pass `'\0'` to notify the parser.
+
+  # Errors
+  The same as `feed_iter`: the characters before the error are consumed.
+
+  # Example
+  ```rust
+  use efjson::{stream_parser::{CoarseToken, StreamParser, TokenInfo}, ParserOption};
+  let mut parser = StreamParser::new(ParserOption::empty());
+  let tokens = parser.feed_coarse("{\"key\": -12.5}").unwrap();
+  assert_eq!(tokens.len(), 8);
+  assert_eq!(tokens[2], CoarseToken::String("key"));
+  assert_eq!(tokens[5], CoarseToken::Whitespace(" "));
+  assert_eq!(tokens[6], CoarseToken::Number("-12.5", TokenInfo::NumberIntegerSign));
+  ```
+  */
+  pub fn feed_coarse<'a>(&mut self, s: &'a str) -> Result<Vec<CoarseToken<'a>>, StreamError> {
+    let mut dest = Vec::new();
+    /* the offset where the last run starts */
+    let mut run = None;
+    let mut offset = 0;
+    let checked = self.needs_tokens();
+    while let Some(c) = s[offset..].chars().next() {
+      let opener = self.raw.state == VAL_COMMENT_MAY_START;
+      if checked {
+        let (comma, token) = self.feed_pair(c)?;
+        if let Some(comma) = comma {
+          run = None;
+          dest.push(CoarseToken::Single(comma));
+        }
+        push_token(&mut dest, &mut run, s, offset, token, opener);
+        offset += c.len_utf8();
+        continue;
+      }
+      let len = self.get_run_length(&s[offset..]);
+      if len == 0 {
+        let ctoken = self.feed_ctoken(c, c.len_utf8())?;
+        let token = Token { c, info: unsafe { super::convert_token(ctoken) } };
+        push_token(&mut dest, &mut run, s, offset, token, opener);
+        /* a character after a lone high surrogate is fed again */
+        if ctoken.is_consumed() {
+          offset += c.len_utf8();
+        }
+        continue;
+      }
+      let info =
+        if self.raw.state == VAL_STRING { TokenInfo::StringNormal } else { TokenInfo::Whitespace };
+      self.skip_run(&s[offset..offset + len]);
+      push_run(&mut dest, &mut run, s, offset, offset + len, info);
+      offset += len;
+    }
+    Ok(dest)
+  }
+}
@@ -0,0 +1,316 @@
+use super::outer::*;
+use super::{ErrorKind, Location, Stage, StreamError, StreamParser, Token};
+use crate::ParserOption;
+
+/**
+The encoding of JSON text.
+
+See RFC 4627, section 3.
+*/
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Encoding {
+  Utf8,
+  Utf16Le,
+  Utf16Be,
+  Utf32Le,
+  Utf32Be,
+}
+impl Encoding {
+  /**
+  Detect the encoding from the first bytes of the input.
+
+  The byte order mark is checked first, then the pattern of null bytes
+  (since the first two characters of JSON text are always ASCII).
+
+  # Note
+  `head` should contain the first four bytes, or the whole input if it is shorter.
+  */
+  pub fn detect(head: &[u8]) -> Encoding {
+    match head {
+      [0xEF, 0xBB, 0xBF, ..] => Encoding::Utf8,
+      [0x00, 0x00, 0xFE, 0xFF, ..] => Encoding::Utf32Be,
+      [0xFF, 0xFE, 0x00, 0x00, ..] => Encoding::Utf32Le,
+      [0xFE, 0xFF, ..] => Encoding::Utf16Be,
+      [0xFF, 0xFE, ..] => Encoding::Utf16Le,
+      [0x00, 0x00, 0x00, _, ..] => Encoding::Utf32Be,
+      [_, 0x00, 0x00, 0x00, ..] => Encoding::Utf32Le,
+      [0x00, _, ..] => Encoding::Utf16Be,
+      [_, 0x00, ..] => Encoding::Utf16Le,
+      _ => Encoding::Utf8,
+    }
+  }
+  /** Get the byte order mark of the encoding */
+  pub fn bom(self) -> &'static [u8] {
+    match self {
+      Encoding::Utf8 => &[0xEF, 0xBB, 0xBF],
+      Encoding::Utf16Le => &[0xFF, 0xFE],
+      Encoding::Utf16Be => &[0xFE, 0xFF],
+      Encoding::Utf32Le => &[0xFF, 0xFE, 0x00, 0x00],
+      Encoding::Utf32Be => &[0x00, 0x00, 0xFE, 0xFF],
+    }
+  }
+  /** Get the size of a code unit in bytes */
+  pub fn unit_size(self) -> usize {
+    match self {
+      Encoding::Utf8 => 1,
+      Encoding::Utf16Le | Encoding::Utf16Be => 2,
+      Encoding::Utf32Le | Encoding::Utf32Be => 4,
+    }
+  }
+}
+
+/**
+A layer over `StreamParser` accepting UTF-8, UTF-16 or UTF-32 bytes.
+
+The encoding is detected from the first four bytes (unless it is specified),
+and a leading byte order mark is skipped.
+Code points may be split across chunks.
+
+# Note
+The `byte_position` of tokens and errors counts the bytes of the original input.
+
+# Example
+```rust
+use efjson::{stream_parser::{DecodingStreamParser, Encoding}, ParserOption};
+let mut parser = DecodingStreamParser::new(ParserOption::all());
+let mut tokens = parser.feed_bytes(&[0xFF, 0xFE, b'[', 0, b'1']).unwrap();
+tokens.extend(parser.feed_bytes(&[0, b']', 0]).unwrap());
+tokens.extend(parser.end().unwrap());
+assert_eq!(parser.get_encoding(), Some(Encoding::Utf16Le));
+assert_eq!(tokens.len(), 4);
+```
+*/
+#[derive(Debug, Clone)]
+pub struct DecodingStreamParser {
+  parser: StreamParser,
+  encoding: Option<Encoding>,
+  head: [u8; 4],
+  head_len: u8,
+  unit: [u8; 4],
+  unit_len: u8,
+  utf16: Utf16Decoder,
+  /** the UTF-16 unit after a lone high surrogate, which is decoded at the next call */
+  held: Option<u16>,
+}
+
+impl DecodingStreamParser {
+  /** Create a parser which detects the encoding */
+  pub fn new(option: ParserOption) -> Self {
+    Self {
+      parser: StreamParser::new(option),
+      encoding: None,
+      head: [0; 4],
+      head_len: 0,
+      unit: [0; 4],
+      unit_len: 0,
+      utf16: Utf16Decoder::default(),
+      held: None,
+    }
+  }
+  /** Create a parser with the specified encoding */
+  pub fn with_encoding(option: ParserOption, encoding: Encoding) -> Self {
+    let mut parser = Self::new(option);
+    parser.encoding = Some(encoding);
+    parser
+  }
+
+  /** Get the encoding, `None` if it has not been detected yet */
+  pub fn get_encoding(&self) -> Option<Encoding> {
+    self.encoding
+  }
+  pub fn get_line(&self) -> usize {
+    self.parser.get_line()
+  }
+  pub fn get_column(&self) -> usize {
+    self.parser.get_column()
+  }
+  pub fn get_position(&self) -> usize {
+    self.parser.get_position()
+  }
+  pub fn get_byte_position(&self) -> usize {
+    self.parser.get_byte_position()
+  }
+  pub fn get_location(&self) -> Location {
+    self.parser.get_location()
+  }
+  pub fn get_stage(&self) -> Stage {
+    self.parser.get_stage()
+  }
+
+  /**
+  Feed a chunk of bytes to the parser and return a vector of tokens.
+
+  The first four bytes are held back until the encoding (and byte order mark) can be decided.
+
+  # Errors
+  Invalid input (including lone surrogates) is reported as `ErrorKind::InvalidInputUtf`,
+  and the invalid sequence is discarded.
+  A UTF-16 unit which breaks a surrogate pair is not discarded, it is decoded at the next call.
+  Other errors are the same as `StreamParser::feed_one`.
+  The function will not roll back the bytes that have already been fed.
+
+  # Example
+  ```rust
+  use efjson::{stream_parser::{DecodingStreamParser, Encoding, ErrorKind, TokenInfo}, ParserOption};
+  /* "\"", a lone high surrogate and "\"" in UTF-16LE */
+  let mut parser = DecodingStreamParser::with_encoding(ParserOption::empty(), Encoding::Utf16Le);
+  let err = parser.feed_bytes(&[0x22, 0, 0x00, 0xD8, 0x22, 0]).unwrap_err();
+  assert_eq!((err.kind, err.byte_position), (ErrorKind::InvalidInputUtf, 2));
+  let tokens = parser.end().unwrap();
+  assert_eq!(tokens[0].info, TokenInfo::StringEnd);
+  assert_eq!(parser.get_byte_position(), 6);
+  ```
+  */
+  pub fn feed_bytes(&mut self, bytes: &[u8]) -> Result<Vec<Token>, StreamError> {
+    let mut tokens = Vec::with_capacity(bytes.len());
+    if let Some(unit) = self.held.take() {
+      self.feed_utf16(unit, &mut tokens)?;
+    }
+    let mut rest = bytes;
+    if self.head_len < 4 {
+      let n = rest.len().min(4 - self.head_len as usize);
+      self.head[self.head_len as usize..self.head_len as usize + n].copy_from_slice(&rest[..n]);
+      self.head_len += n as u8;
+      rest = &rest[n..];
+      if self.head_len < 4 {
+        return Ok(tokens);
+      }
+      self.flush_head(&mut tokens)?;
+    }
+    let encoding = self.encoding.expect("the encoding is decided when the head is flushed");
+    for &b in rest {
+      self.feed_byte(encoding, b, &mut tokens)?;
+    }
+    Ok(tokens)
+  }
+  /**
+  Notify the parser that the input is ended, and return the remaining tokens (including `Eof`).
+
+  # Errors
+  An incomplete code unit or surrogate pair is reported as `ErrorKind::InvalidInputUtf`.
+
+  # Example
+  ```rust
+  use efjson::{stream_parser::{DecodingStreamParser, Encoding}, ParserOption};
+  /* "\u{1F600}" in UTF-16LE, a surrogate pair followed by a plain unit */
+  let bytes = [0x22, 0, 0x3D, 0xD8, 0x00, 0xDE, 0x22, 0];
+  let mut parser = DecodingStreamParser::with_encoding(ParserOption::empty(), Encoding::Utf16Le);
+  let mut tokens = parser.feed_bytes(&bytes).unwrap();
+  tokens.extend(parser.end().unwrap());
+  assert_eq!(tokens.iter().map(|t| t.c).collect::<String>(), "\"\u{1F600}\"\0");
+  ```
+  */
+  pub fn end(&mut self) -> Result<Vec<Token>, StreamError> {
+    let mut tokens = Vec::new();
+    if self.head_len < 4 {
+      /* the head is not flushed again, even if it fails */
+      self.head_len = 4;
+      self.flush_head(&mut tokens)?;
+    }
+    if let Some(unit) = self.held.take() {
+      self.feed_utf16(unit, &mut tokens)?;
+    }
+    if self.unit_len != 0 || self.utf16.first != 0 {
+      return Err(self.parser.make_error(char::REPLACEMENT_CHARACTER, ErrorKind::InvalidInputUtf));
+    }
+    tokens.push(self.parser.feed_one('\0')?);
+    Ok(tokens)
+  }
+
+  fn flush_head(&mut self, tokens: &mut Vec<Token>) -> Result<(), StreamError> {
+    let head = self.head;
+    let head = &head[..self.head_len as usize];
+    let encoding = *self.encoding.get_or_insert_with(|| Encoding::detect(head));
+    let bom = encoding.bom();
+    let head = if head.starts_with(bom) {
+      self.parser.byte_position += bom.len();
+      &head[bom.len()..]
+    } else {
+      head
+    };
+    for &b in head {
+      self.feed_byte(encoding, b, tokens)?;
+    }
+    Ok(())
+  }
+  fn feed_byte(
+    &mut self,
+    encoding: Encoding,
+    b: u8,
+    tokens: &mut Vec<Token>,
+  ) -> Result<(), StreamError> {
+    if encoding == Encoding::Utf8 {
+      if let Some(c) = self.parser.decode_byte(b)? {
+        self.parser.feed_one_into(c, tokens)?;
+      }
+      return Ok(());
+    }
+
+    self.unit[self.unit_len as usize] = b;
+    self.unit_len += 1;
+    if (self.unit_len as usize) < encoding.unit_size() {
+      return Ok(());
+    }
+    self.unit_len = 0;
+    let c = match encoding {
+      Encoding::Utf16Le | Encoding::Utf16Be => {
+        let unit = [self.unit[0], self.unit[1]];
+        let unit = if encoding == Encoding::Utf16Le {
+          u16::from_le_bytes(unit)
+        } else {
+          u16::from_be_bytes(unit)
+        };
+        return self.feed_utf16(unit, tokens);
+      }
+      _ => {
+        let code = if encoding == Encoding::Utf32Le {
+          u32::from_le_bytes(self.unit)
+        } else {
+          u32::from_be_bytes(self.unit)
+        };
+        match char::from_u32(code) {
+          Some(c) => c,
+          None => return Err(self.discard(4)),
+        }
+      }
+    };
+    self.feed_char(c, 4, tokens)
+  }
+  fn feed_utf16(&mut self, unit: u16, tokens: &mut Vec<Token>) -> Result<(), StreamError> {
+    if self.utf16.first != 0 && !(0xDC00..=0xDFFF).contains(&unit) {
+      /* only the high surrogate is invalid */
+      self.held = Some(unit);
+      return Err(self.discard(2));
+    }
+    let mut code = 0u32;
+    match unsafe { efjsonUtf16Decoder_feed(&mut self.utf16, &mut code, unit) } {
+      0 => Ok(()),
+      1 => {
+        let c = unsafe { char::from_u32_unchecked(code) };
+        self.feed_char(c, c.len_utf16() * 2, tokens)
+      }
+      _ => Err(self.discard(2)),
+    }
+  }
+  fn feed_char(&mut self, c: char, len: usize, tokens: &mut Vec<Token>) -> Result<(), StreamError> {
+    let (comma, token) = self.parser.feed_pair_sized(c, len, true)?;
+    tokens.extend(comma);
+    tokens.push(token);
+    Ok(())
+  }
+  fn discard(&mut self, len: usize) -> StreamError {
+    let err = self.parser.make_error(char::REPLACEMENT_CHARACTER, ErrorKind::InvalidInputUtf);
+    self.parser.byte_position += len;
+    self.utf16 = Utf16Decoder::default();
+    err
+  }
+}
+
+impl DecodingStreamParser {
+  pub fn parse(option: ParserOption, bytes: &[u8]) -> Result<Vec<Token>, StreamError> {
+    let mut parser = DecodingStreamParser::new(option);
+    let mut tokens = parser.feed_bytes(bytes)?;
+    tokens.extend(parser.end()?);
+    Ok(tokens)
+  }
+}
@@ -0,0 +1,260 @@
+use super::outer::*;
+use super::{convert_token, Category, StreamParser, TokenInfo};
+use crate::ParserOption;
+
+bitflags::bitflags! {
+  /** What may come next in the input, see `StreamParser::expected` */
+  #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+  pub struct ExpectedSet: u32 {
+    /* << between tokens >> */
+    /** whitespace (or the text ignored by `ParserOption::TRAILING_TEXT`) */
+    const WHITESPACE    = 0x0000001;
+    /** the start of a comment */
+    const COMMENT       = 0x0000002;
+    /** the end of input (`'\0'`) */
+    const END           = 0x0000004;
+    /* << values >> */
+    /** an object (`{`) */
+    const OBJECT        = 0x0000008;
+    /** an array (`[`) */
+    const ARRAY         = 0x0000010;
+    /** a string (`"`, or `'` with `ParserOption::SINGLE_QUOTE`) */
+    const STRING        = 0x0000020;
+    /** a number (a digit or a sign, or `.` with `ParserOption::EMPTY_INTEGER`) */
+    const NUMBER        = 0x0000040;
+    /** a literal (`true`, `false`, `null`, and `NaN`, `Infinity` or the Python literals if enabled) */
+    const LITERAL       = 0x0000080;
+    /** a key (a string, or an identifier with `ParserOption::IDENTIFIER_KEY`) */
+    const KEY           = 0x0000100;
+    /* << punctuators >> */
+    /** `:` after a key */
+    const COLON         = 0x0000200;
+    /** `,` after an element or a member */
+    const COMMA         = 0x0000400;
+    /** `}` */
+    const OBJECT_END    = 0x0000800;
+    /** `]` */
+    const ARRAY_END     = 0x0001000;
+    /* << inside values >> */
+    /** the next digit of a number */
+    const DIGIT         = 0x0002000;
+    /** `.` starting the fraction of a number */
+    const FRACTION      = 0x0004000;
+    /** `e` or `E` starting the exponent of a number */
+    const EXPONENT      = 0x0008000;
+    /** the sign of an exponent */
+    const EXPONENT_SIGN = 0x0010000;
+    /** the prefix of a hexadecimal, octal or binary integer (`x`, `o` or `b` after `0`) */
+    const RADIX         = 0x0020000;
+    /** the next character of a literal */
+    const LITERAL_NEXT  = 0x0040000;
+    /** a character of a string */
+    const STRING_CHAR   = 0x0080000;
+    /** the closing quote of a string */
+    const STRING_END    = 0x0100000;
+    /** `\` starting an escape sequence (in a string or an identifier) */
+    const ESCAPE        = 0x0200000;
+    /** the next character of an escape sequence */
+    const ESCAPE_NEXT   = 0x0400000;
+    /** the next character of an identifier key */
+    const IDENTIFIER_NEXT = 0x0800000;
+    /** the next character of a comment (including the end of the comment) */
+    const COMMENT_NEXT  = 0x1000000;
+  }
+}
+impl ExpectedSet {
+  /** Get the description of a single item (for example, `"key"` or ``"`}`"``), `None` for others */
+  pub fn describe(self) -> Option<&'static str> {
+    Some(match self {
+      Self::WHITESPACE => "whitespace",
+      Self::COMMENT => "comment",
+      Self::END => "end of input",
+      Self::OBJECT => "object",
+      Self::ARRAY => "array",
+      Self::STRING => "string",
+      Self::NUMBER => "number",
+      Self::LITERAL => "literal",
+      Self::KEY => "key",
+      Self::COLON => "`:`",
+      Self::COMMA => "`,`",
+      Self::OBJECT_END => "`}`",
+      Self::ARRAY_END => "`]`",
+      Self::DIGIT => "digit",
+      Self::FRACTION => "`.`",
+      Self::EXPONENT => "`e`",
+      Self::EXPONENT_SIGN => "sign",
+      Self::RADIX => "radix prefix",
+      Self::LITERAL_NEXT => "rest of literal",
+      Self::STRING_CHAR => "character",
+      Self::STRING_END => "closing quote",
+      Self::ESCAPE => "escape",
+      Self::ESCAPE_NEXT => "rest of escape",
+      Self::IDENTIFIER_NEXT => "rest of identifier",
+      Self::COMMENT_NEXT => "rest of comment",
+      _ => return None,
+    })
+  }
+  /** Whether the number being parsed may end here (that is, the next character may follow a number) */
+  pub fn may_end_number(self) -> bool {
+    self.intersects(Self::WHITESPACE | Self::COMMA | Self::OBJECT_END | Self::ARRAY_END | Self::END)
+  }
+}
+/** List the items separated by "or", for example "key or `}`" */
+impl std::fmt::Display for ExpectedSet {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    if self.is_empty() {
+      return f.write_str("nothing");
+    }
+    for (i, item) in self.iter().enumerate() {
+      if i != 0 {
+        f.write_str(" or ")?;
+      }
+      f.write_str(item.describe().unwrap_or_default())?;
+    }
+    Ok(())
+  }
+}
+
+/** Whether the state is inside a number */
+fn in_number(state: u8) -> bool {
+  (VAL_NUMBER..=VAL_NUMBER_EXPONENT).contains(&state)
+    || (VAL_NUMBER_INFINITY..=VAL_NUMBER_BIN).contains(&state)
+}
+/** Whether the state is inside a comment */
+fn in_comment(state: u8) -> bool {
+  (VAL_COMMENT_MAY_START..=VAL_MULTI_LINE_COMMENT_MAY_END).contains(&state)
+}
+
+/** Classify the token accepted at the location and state */
+fn classify(info: TokenInfo, location: u8, state: u8) -> ExpectedSet {
+  let at_key = matches!(location, LOC_KEY_FIRST_START | LOC_KEY_START) && state == VAL_EMPTY;
+  match info {
+    TokenInfo::Whitespace => ExpectedSet::WHITESPACE,
+    TokenInfo::Eof => ExpectedSet::END,
+    TokenInfo::Null(0, _)
+    | TokenInfo::True(0, _)
+    | TokenInfo::False(0, _)
+    | TokenInfo::NumberNan(0, _)
+    | TokenInfo::NumberInfinity(0, _) => ExpectedSet::LITERAL,
+    TokenInfo::Null(..)
+    | TokenInfo::True(..)
+    | TokenInfo::False(..)
+    | TokenInfo::NumberNan(..)
+    | TokenInfo::NumberInfinity(..) => ExpectedSet::LITERAL_NEXT,
+    TokenInfo::StringStart if at_key => ExpectedSet::KEY,
+    TokenInfo::StringStart => ExpectedSet::STRING,
+    TokenInfo::StringEnd => ExpectedSet::STRING_END,
+    TokenInfo::StringNormal => ExpectedSet::STRING_CHAR,
+    TokenInfo::StringEscapeStart => ExpectedSet::ESCAPE,
+    TokenInfo::StringEscape(_)
+    | TokenInfo::StringEscapeUnicodeStart
+    | TokenInfo::StringEscapeUnicode(..)
+    | TokenInfo::StringNextLine
+    | TokenInfo::StringEscapeHexStart
+    | TokenInfo::StringEscapeHex(..)
+    | TokenInfo::StringEscapeSurrogate(_) => ExpectedSet::ESCAPE_NEXT,
+    _ if info.get_category() == Category::Number && !in_number(state) => ExpectedSet::NUMBER,
+    TokenInfo::NumberFractionStart => ExpectedSet::FRACTION,
+    TokenInfo::NumberExponentStart => ExpectedSet::EXPONENT,
+    TokenInfo::NumberExponentSign => ExpectedSet::EXPONENT_SIGN,
+    TokenInfo::NumberHexStart | TokenInfo::NumberOctStart | TokenInfo::NumberBinStart => {
+      ExpectedSet::RADIX
+    }
+    TokenInfo::NumberIntegerDigit
+    | TokenInfo::NumberFractionDigit
+    | TokenInfo::NumberExponentDigit
+    | TokenInfo::NumberIntegerSign
+    | TokenInfo::NumberHex
+    | TokenInfo::NumberOct
+    | TokenInfo::NumberBin => ExpectedSet::DIGIT,
+    TokenInfo::ObjectStart => ExpectedSet::OBJECT,
+    TokenInfo::ObjectNext | TokenInfo::ArrayNext => ExpectedSet::COMMA,
+    TokenInfo::ObjectValueStart => ExpectedSet::COLON,
+    TokenInfo::ObjectEnd => ExpectedSet::OBJECT_END,
+    TokenInfo::ArrayStart => ExpectedSet::ARRAY,
+    TokenInfo::ArrayEnd => ExpectedSet::ARRAY_END,
+    TokenInfo::IdentifierNormal | TokenInfo::IdentifierEscapeStart(0, _) if at_key => {
+      ExpectedSet::KEY
+    }
+    TokenInfo::IdentifierNormal => ExpectedSet::IDENTIFIER_NEXT,
+    TokenInfo::IdentifierEscapeStart(0, _) => ExpectedSet::ESCAPE,
+    TokenInfo::IdentifierEscapeStart(..) | TokenInfo::IdentifierEscape(..) => {
+      ExpectedSet::ESCAPE_NEXT
+    }
+    _ if in_comment(state) => ExpectedSet::COMMENT_NEXT,
+    _ => ExpectedSet::COMMENT,
+  }
+}
+
+/** Probe the characters which are classified differently, and restore the parser after each one */
+fn probe(raw: &mut RawStreamParser) -> ExpectedSet {
+  let snapshot = raw.snapshot();
+  let mut set = ExpectedSet::empty();
+  /* the classes of non-ASCII characters are the same as some ASCII ones */
+  for u in 0..0x80u32 {
+    let mut ctoken = unsafe { efjsonStreamParser_feedOne(raw, u) };
+    let (mut location, mut state) = (snapshot.location, snapshot.state);
+    if ctoken.r#type != 0 && !ctoken.is_consumed() {
+      /* a lone high surrogate is reported before the character, which is classified by its own token */
+      (location, state) = (raw.location, raw.state);
+      ctoken = unsafe { efjsonStreamParser_feedOne(raw, u) };
+    }
+    if ctoken.r#type != 0 {
+      set |= classify(unsafe { convert_token(ctoken) }, location, state);
+    }
+    raw.restore(&snapshot);
+  }
+  set
+}
+
+impl StreamParser {
+  /**
+  Get what may come next in the input, without consuming it.
+
+  The set is computed from the current state and the enabled options
+  (for example, `]` after `,` only with `ParserOption::TRAILING_COMMA_IN_ARRAY`,
+  and values after values with `ParserOption::MISSING_COMMA`).
+
+  # Note
+  `Limits` and the checks of I-JSON are not taken into account,
+  nor is an incomplete UTF-8 sequence fed by `feed_byte`.
+
+  # Example
+  ```rust
+  use efjson::{stream_parser::{ExpectedSet, StreamParser}, ParserOption};
+  let mut parser = StreamParser::new(ParserOption::empty());
+  parser.feed("{").unwrap();
+  assert_eq!(parser.expected(), ExpectedSet::WHITESPACE | ExpectedSet::KEY | ExpectedSet::OBJECT_END);
+  assert_eq!(parser.expected().to_string(), "whitespace or key or `}`");
+
+  parser.feed("\"a\": [1").unwrap();
+  let expected = parser.expected();
+  assert!(expected.contains(ExpectedSet::DIGIT | ExpectedSet::FRACTION | ExpectedSet::EXPONENT));
+  assert!(expected.may_end_number());
+
+  parser.feed(",").unwrap();
+  assert!(!parser.expected().contains(ExpectedSet::ARRAY_END));
+  let mut parser = StreamParser::new(ParserOption::TRAILING_COMMA_IN_ARRAY);
+  parser.feed("[1,").unwrap();
+  assert!(parser.expected().contains(ExpectedSet::ARRAY_END));
+  ```
+  */
+  pub fn expected(&self) -> ExpectedSet {
+    let mut raw = self.raw.clone();
+    let mut set = probe(&mut raw);
+    /* see `feed_pair` */
+    let after_value =
+      matches!(raw.location, LOC_VALUE_END | LOC_ELEMENT_END) || in_number(raw.state);
+    if raw.option & ParserOption::MISSING_COMMA.bits() != 0 && raw.len != 0 && after_value {
+      let (position, line, column) = (raw.position, raw.line, raw.column);
+      let ctoken = unsafe { efjsonStreamParser_feedOne(&mut raw, ',' as u32) };
+      (raw.position, raw.line, raw.column) = (position, line, column);
+      if ctoken.r#type != 0
+        && matches!(unsafe { convert_token(ctoken) }, TokenInfo::ArrayNext | TokenInfo::ObjectNext)
+      {
+        set |= probe(&mut raw) - ExpectedSet::WHITESPACE - ExpectedSet::COMMENT;
+      }
+    }
+    set
+  }
+}
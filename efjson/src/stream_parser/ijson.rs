@@ -0,0 +1,207 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use super::{Category, ErrorKind, StreamParser, Token, TokenInfo};
+
+/** Whether `c` is a noncharacter (U+FDD0 to U+FDEF, and the last two code points of each plane) */
+fn is_noncharacter(c: char) -> bool {
+  let u = c as u32;
+  (0xFDD0..=0xFDEF).contains(&u) || u & 0xFFFE == 0xFFFE
+}
+
+/** Get the character a token adds to a string or an identifier (after unescaping) */
+fn string_char(token: &Token) -> Option<char> {
+  match token.info {
+    TokenInfo::StringNormal | TokenInfo::IdentifierNormal => Some(token.c),
+    TokenInfo::StringEscape(c)
+    | TokenInfo::StringEscapeUnicode(_, Some(c))
+    | TokenInfo::StringEscapeHex(_, Some(c))
+    | TokenInfo::IdentifierEscape(_, Some(c)) => Some(c),
+    _ => None,
+  }
+}
+
+/** Whether the number can be represented as a (nonzero if its digits are) IEEE 754 double */
+fn is_number_in_range(number: &str) -> bool {
+  let digits = number.trim_start_matches(['+', '-']);
+  let radix = match digits.get(..2) {
+    Some("0x" | "0X") => 16,
+    Some("0o" | "0O") => 8,
+    Some("0b" | "0B") => 2,
+    _ => 10,
+  };
+  if radix != 10 {
+    let value = digits[2..]
+      .chars()
+      .fold(0.0, |value, c| value * radix as f64 + c.to_digit(radix).unwrap_or(0) as f64);
+    return value.is_finite();
+  }
+  let value = digits.parse::<f64>().unwrap_or(f64::INFINITY);
+  let mantissa = digits.split(['e', 'E']).next().unwrap_or_default();
+  value.is_finite() && (value != 0.0 || !mantissa.contains(|c: char| matches!(c, '1'..='9')))
+}
+
+/** Check the semantics of I-JSON from the tokens, see `StreamParser::enable_ijson` */
+#[derive(Debug, Clone, Default)]
+pub(super) struct IJsonChecker {
+  /**
+  the keys of each object, `None` for arrays (and the containers entered before the checker),
+  shared with checkpoints and copied on write
+  */
+  pub(super) keys: Vec<Option<Arc<HashSet<String>>>>,
+  /** the key being parsed, and whether it is an identifier */
+  pub(super) key: Option<(String, bool)>,
+  /** whether the innermost object expects a key */
+  pub(super) in_key: bool,
+  /** the characters of the number being parsed */
+  pub(super) number: Option<String>,
+}
+impl IJsonChecker {
+  /** Create a checker at the depth `depth`, where the keys parsed before are unknown */
+  pub(super) fn with_depth(depth: usize) -> Self {
+    Self { keys: vec![None; depth], ..Default::default() }
+  }
+  pub(super) fn clear(&mut self) {
+    self.keys.clear();
+    self.key = None;
+    self.in_key = false;
+    self.number = None;
+  }
+
+  fn is_duplicate(&self, key: &str) -> bool {
+    matches!(self.keys.last(), Some(Some(keys)) if keys.contains(key))
+  }
+  fn finish_key(&mut self) {
+    if let (Some((key, _)), Some(Some(keys))) = (self.key.take(), self.keys.last_mut()) {
+      Arc::make_mut(keys).insert(key);
+    }
+  }
+
+  /** Check the token, the checker is not changed */
+  pub(super) fn check(&self, token: &Token) -> Result<(), ErrorKind> {
+    let category = token.info.get_category();
+    if let Some(number) = self.number.as_ref() {
+      if category != Category::Number && !is_number_in_range(number) {
+        return Err(ErrorKind::NumberOutOfRange);
+      }
+    }
+    match (&self.key, token.info) {
+      (Some((key, true)), _) if category != Category::Identifier && self.is_duplicate(key) => {
+        return Err(ErrorKind::DuplicateKey);
+      }
+      (Some((key, false)), TokenInfo::StringEnd) if self.is_duplicate(key) => {
+        return Err(ErrorKind::DuplicateKey);
+      }
+      (_, TokenInfo::NumberNan(..) | TokenInfo::NumberInfinity(..)) => {
+        return Err(ErrorKind::NumberOutOfRange);
+      }
+      (_, TokenInfo::StringEscapeSurrogate(0xDC00..=0xDFFF)) => {
+        return Err(ErrorKind::InvalidEscapedUtf);
+      }
+      (_, TokenInfo::StringEscapeSurrogate(_)) => return Err(ErrorKind::IncompleteSurrogatePair),
+      _ => {}
+    }
+    match string_char(token) {
+      Some(c) if is_noncharacter(c) => Err(ErrorKind::NoncharacterForbidden),
+      _ => Ok(()),
+    }
+  }
+  /** Update the checker after the token passes `check` */
+  pub(super) fn update(&mut self, token: &Token) {
+    let category = token.info.get_category();
+    if category == Category::Number {
+      self.number.get_or_insert_with(String::new).push(token.c);
+    } else {
+      self.number = None;
+    }
+    if matches!(self.key, Some((_, true))) && category != Category::Identifier {
+      self.finish_key();
+    }
+    match token.info {
+      TokenInfo::ObjectStart => {
+        self.keys.push(Some(Default::default()));
+        self.in_key = true;
+      }
+      TokenInfo::ObjectNext => self.in_key = true,
+      TokenInfo::ObjectValueStart => self.in_key = false,
+      TokenInfo::ArrayStart => {
+        self.keys.push(None);
+        self.in_key = false;
+      }
+      TokenInfo::ObjectEnd | TokenInfo::ArrayEnd => {
+        self.keys.pop();
+        self.in_key = false;
+      }
+      TokenInfo::StringStart if self.in_key => self.key = Some((String::new(), false)),
+      TokenInfo::StringEnd if self.key.is_some() => self.finish_key(),
+      TokenInfo::IdentifierNormal | TokenInfo::IdentifierEscapeStart(..)
+        if self.in_key && self.key.is_none() =>
+      {
+        self.key = Some((String::new(), true));
+      }
+      _ => {}
+    }
+    if let (Some(c), Some((key, _))) = (string_char(token), self.key.as_mut()) {
+      key.push(c);
+    }
+  }
+}
+
+impl StreamParser {
+  /**
+  Enable the checks of I-JSON (RFC 7493) on top of the syntax accepted by the option,
+  which is intended to be used with `ParserOption::empty()` (RFC 8259).
+
+  The parser then rejects:
+  - duplicate keys in an object (`ErrorKind::DuplicateKey`),
+    reported at the closing quote of the key (or the character after an identifier key);
+  - numbers which overflow or underflow to zero as IEEE 754 doubles,
+    and `NaN` or `Infinity` (`ErrorKind::NumberOutOfRange`),
+    reported at the character after the number;
+  - noncharacters in strings and keys, even if escaped (`ErrorKind::NoncharacterForbidden`);
+  - lone surrogates accepted by `ParserOption::LONE_SURROGATE` (`ErrorKind::InvalidEscapedUtf`
+    for a low surrogate, `ErrorKind::IncompleteSurrogatePair` for a high surrogate,
+    reported at the character after it unless the `no-combine-surrogate` feature is enabled).
+
+  Otherwise lone surrogates (in escapes or in the input) are already rejected by the parser.
+
+  # Panics
+  Panics if the parser is inside an array or an object, or a value.
+
+  # Example
+  ```rust
+  use efjson::{stream_parser::{ErrorKind, StreamParser}, ParserOption};
+  let mut parser = StreamParser::new(ParserOption::empty());
+  parser.enable_ijson();
+  let err = parser.feed("{\"id\": 1, \"name\": \"a\", \"id\": 2}").unwrap_err();
+  assert_eq!((err.kind, err.position), (ErrorKind::DuplicateKey, 26));
+
+  let mut parser = StreamParser::new(ParserOption::empty());
+  parser.enable_ijson();
+  let err = parser.feed("[1e308, 1e309]").unwrap_err();
+  assert_eq!((err.kind, err.position), (ErrorKind::NumberOutOfRange, 13));
+
+  #[cfg(not(feature = "no-combine-surrogate"))]
+  {
+    let mut parser = StreamParser::new(ParserOption::LONE_SURROGATE);
+    parser.enable_ijson();
+    assert!(parser.feed(r#"["\uD83D\uDE00", "\uD83D\uDE00"]"#).is_ok());
+    let mut parser = StreamParser::new(ParserOption::LONE_SURROGATE);
+    parser.enable_ijson();
+    let err = parser.feed(r#""\uD83Dx""#).unwrap_err();
+    assert_eq!((err.kind, err.position), (ErrorKind::IncompleteSurrogatePair, 7));
+  }
+  ```
+  */
+  pub fn enable_ijson(&mut self) {
+    assert!(
+      self.raw.len == 0 && self.raw.state == super::outer::VAL_EMPTY,
+      "I-JSON must be enabled outside of values"
+    );
+    self.ijson.get_or_insert_with(Default::default);
+  }
+  /** Whether the checks of I-JSON are enabled, see `enable_ijson` */
+  pub fn is_ijson(&self) -> bool {
+    self.ijson.is_some()
+  }
+}
@@ -0,0 +1,179 @@
+use super::outer::*;
+use super::{Category, ErrorKind, StreamParser, TokenInfo};
+use crate::ParserOption;
+
+/**
+Limits of the resources used by the parser, to protect against malicious input.
+
+Every limit is unlimited by default.
+
+# Example
+```rust
+use efjson::{stream_parser::{ErrorKind, Limits, StreamParser}, ParserOption};
+let mut parser = StreamParser::with_limits(ParserOption::all(), Limits { max_depth: 2, ..Default::default() });
+let err = parser.feed("[[[").unwrap_err();
+assert_eq!((err.kind, err.position), (ErrorKind::TooManyRecursions, 2));
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+  /** the maximum depth of nested arrays and objects */
+  pub max_depth: usize,
+  /** the maximum number of characters of the input */
+  pub max_input_length: usize,
+  /** the maximum number of characters of a string (after unescaping), or an identifier */
+  pub max_string_length: usize,
+  /** the maximum number of characters of a number */
+  pub max_number_length: usize,
+  /** the maximum number of elements of an array */
+  pub max_array_length: usize,
+  /** the maximum number of members of an object */
+  pub max_object_length: usize,
+  /** the maximum number of values (the nodes of `JsonValue`) */
+  pub max_nodes: usize,
+}
+impl Default for Limits {
+  fn default() -> Self {
+    Self {
+      max_depth: usize::MAX,
+      max_input_length: usize::MAX,
+      max_string_length: usize::MAX,
+      max_number_length: usize::MAX,
+      max_array_length: usize::MAX,
+      max_object_length: usize::MAX,
+      max_nodes: usize::MAX,
+    }
+  }
+}
+
+/** The limits and the counters */
+#[derive(Debug, Clone)]
+pub(super) struct Limiter {
+  limits: Limits,
+  /** the length of the current string or number */
+  length: usize,
+  nodes: usize,
+  /** the number of elements of each array or object */
+  counts: Vec<usize>,
+}
+impl Limiter {
+  /** Reset the counters of the current document, see `MultiDocumentParser` */
+  pub(super) fn reset_document(&mut self) {
+    self.length = 0;
+    self.nodes = 0;
+    self.counts.clear();
+  }
+  /** Get the counters, see `StreamParser::save_state` */
+  pub(super) fn get_counters(&self) -> (usize, usize, &[usize]) {
+    (self.length, self.nodes, &self.counts)
+  }
+  pub(super) fn set_counters(&mut self, length: usize, nodes: usize, counts: Vec<usize>) {
+    self.length = length;
+    self.nodes = nodes;
+    self.counts = counts;
+  }
+  /** Check the length of the input before the character at `position` is fed */
+  pub(super) fn check_input(&self, c: char, position: usize) -> Result<(), ErrorKind> {
+    if c != '\0' && position >= self.limits.max_input_length {
+      Err(ErrorKind::InputTooLong)
+    } else {
+      Ok(())
+    }
+  }
+  /** `prev` is the state before the token */
+  pub(super) fn check(
+    &mut self,
+    prev: &RawSnapshot,
+    depth: usize,
+    info: TokenInfo,
+  ) -> Result<(), ErrorKind> {
+    let category = info.get_category();
+    let length = if prev.state == VAL_EMPTY { 0 } else { self.length };
+    let length = match info {
+      TokenInfo::StringNormal
+      | TokenInfo::StringEscape(_)
+      | TokenInfo::StringEscapeUnicode(_, Some(_))
+      | TokenInfo::StringEscapeHex(_, Some(_))
+      | TokenInfo::StringEscapeSurrogate(_)
+      | TokenInfo::IdentifierNormal
+      | TokenInfo::IdentifierEscape(_, Some(_)) => {
+        if length >= self.limits.max_string_length {
+          return Err(ErrorKind::StringTooLong);
+        }
+        length + 1
+      }
+      _ if category == Category::Number => {
+        if length >= self.limits.max_number_length {
+          return Err(ErrorKind::NumberTooLong);
+        }
+        length + 1
+      }
+      _ => length,
+    };
+
+    let is_container = matches!(info, TokenInfo::ArrayStart | TokenInfo::ObjectStart);
+    if is_container && depth > self.limits.max_depth {
+      return Err(ErrorKind::TooManyRecursions);
+    }
+    let is_value = prev.state == VAL_EMPTY
+      && matches!(
+        prev.location,
+        LOC_ROOT_START | LOC_VALUE_START | LOC_ELEMENT_FIRST_START | LOC_ELEMENT_START
+      )
+      && (is_container
+        || matches!(
+          category,
+          Category::Null | Category::Boolean | Category::String | Category::Number
+        ));
+    if is_value {
+      if self.nodes >= self.limits.max_nodes {
+        return Err(ErrorKind::TooManyNodes);
+      }
+      if prev.location != LOC_ROOT_START {
+        let max = if prev.location == LOC_VALUE_START {
+          self.limits.max_object_length
+        } else {
+          self.limits.max_array_length
+        };
+        let count = self.counts.last_mut().expect("a count is kept for each open container");
+        if *count >= max {
+          return Err(ErrorKind::TooManyElements);
+        }
+        *count += 1;
+      }
+      self.nodes += 1;
+    }
+
+    self.length = length;
+    match info {
+      TokenInfo::ArrayStart | TokenInfo::ObjectStart => self.counts.push(0),
+      TokenInfo::ArrayEnd | TokenInfo::ObjectEnd => {
+        self.counts.pop();
+      }
+      _ => {}
+    }
+    Ok(())
+  }
+}
+
+impl StreamParser {
+  /**
+  Create a parser with limits.
+
+  # Errors
+  Once a limit is exceeded, the parser returns a `StreamError`
+  (`ErrorKind::TooManyRecursions` for `max_depth`, see `ErrorKind::is_limit_exceeded` for others),
+  and the state machine remains unchanged.
+  */
+  pub fn with_limits(option: ParserOption, limits: Limits) -> Self {
+    let mut parser = StreamParser::new(option);
+    if limits != Limits::default() {
+      parser.limits = Some(Box::new(Limiter { limits, length: 0, nodes: 0, counts: Vec::new() }));
+    }
+    parser
+  }
+  /** Get the limits of the parser */
+  pub fn get_limits(&self) -> Limits {
+    self.limits.as_ref().map_or_else(Limits::default, |limiter| limiter.limits)
+  }
+}
@@ -0,0 +1,1324 @@
+mod outer {
+  pub type StackLength = std::ffi::c_uint;
+  pub type Position = usize;
+  #[repr(C)]
+  #[derive(Clone, Copy)]
+  pub struct Token {
+    pub r#type: u8,
+    _dummy: u8,
+    pub index: u8,
+    pub done: u8,
+    pub extra: u32,
+  }
+  impl Token {
+    /**
+    Whether the character is consumed, which is false only for a lone high surrogate reported before it
+    (see `efjsonType_STRING_ESCAPE_SURROGATE`), then the character must be fed again.
+    */
+    pub fn is_consumed(&self) -> bool {
+      self.r#type != 0x5A || self.done != 0
+    }
+  }
+  unsafe extern "C" {
+    pub fn efjson_stringifyError(error: u8) -> *const std::ffi::c_char;
+    pub fn efjson_stringifyCategory(category: u8) -> *const std::ffi::c_char;
+    pub fn efjson_stringifyType(r#type: u16) -> *const std::ffi::c_char;
+    pub fn efjson_stringifyLocation(location: u8) -> *const std::ffi::c_char;
+
+    pub fn efjsonStreamParser_init(parser: *mut RawStreamParser, option: u32) -> ();
+    /* they are macros with a fixed stack */
+    #[cfg(not(feature = "fixed-stack"))]
+    pub fn efjsonStreamParser_deinit(parser: *mut RawStreamParser) -> ();
+    #[cfg(not(feature = "fixed-stack"))]
+    pub fn efjsonStreamParser_initCopy(
+      parser: *mut RawStreamParser,
+      src: *const RawStreamParser,
+    ) -> std::ffi::c_int;
+
+    pub fn efjsonStreamParser_feedOne(parser: *mut RawStreamParser, u: u32) -> Token;
+    pub fn efjsonStreamParser_feed(
+      parser: *mut RawStreamParser,
+      dest: *mut Token,
+      src: *const u32,
+      len: usize,
+    ) -> usize;
+
+    pub fn efjsonStreamParser_getLine(parser: *const RawStreamParser) -> Position;
+    pub fn efjsonStreamParser_getColumn(parser: *const RawStreamParser) -> Position;
+    pub fn efjsonStreamParser_getPosition(parser: *const RawStreamParser) -> Position;
+    pub fn efjsonStreamParser_getLocation(parser: *const RawStreamParser) -> super::Location;
+    pub fn efjsonStreamParser_getStage(parser: *const RawStreamParser) -> std::ffi::c_int;
+
+    /* the allocator of the C parser, used to restore the stack */
+    #[cfg(not(feature = "fixed-stack"))]
+    pub fn malloc(size: usize) -> *mut std::ffi::c_void;
+    #[cfg(not(feature = "fixed-stack"))]
+    pub fn free(ptr: *mut std::ffi::c_void);
+
+    pub fn efjsonUtf8Decoder_feed(
+      decoder: *mut Utf8Decoder,
+      result: *mut u32,
+      c: u8,
+    ) -> std::ffi::c_int;
+    pub fn efjsonUtf16Decoder_feed(
+      decoder: *mut Utf16Decoder,
+      result: *mut u32,
+      c: u16,
+    ) -> std::ffi::c_int;
+  }
+
+  /** Parse the size passed by `build.rs` */
+  #[cfg(feature = "fixed-stack")]
+  const fn parse_size(s: &str) -> usize {
+    let (bytes, mut i, mut size) = (s.as_bytes(), 0, 0);
+    while i < bytes.len() {
+      size = size * 10 + (bytes[i] - b'0') as usize;
+      i += 1;
+    }
+    size
+  }
+  /** The size of the stack in bytes with the feature `fixed-stack` (`EFJSON_CONF_FIXED_STACK`) */
+  #[cfg(feature = "fixed-stack")]
+  pub const FIXED_STACK: usize = parse_size(env!("EFJSON_FIXED_STACK"));
+
+  /** The C state machine, see `efjsonStreamParser` */
+  #[repr(C)]
+  #[derive(Debug)]
+  #[cfg_attr(feature = "fixed-stack", derive(Clone))]
+  pub struct RawStreamParser {
+    pub position: Position,
+    pub line: Position,
+    pub column: Position,
+    pub option: u32,
+    pub location: u8,
+    pub state: u8,
+    pub flag: u8,
+    pub substate: u8,
+    pub escape: u16,
+    #[cfg(not(feature = "no-combine-surrogate"))]
+    pub prev_pair: u16,
+
+    pub len: StackLength,
+    #[cfg(not(feature = "fixed-stack"))]
+    pub cap: StackLength,
+    #[cfg(not(feature = "fixed-stack"))]
+    pub stack: *mut u8,
+    #[cfg(feature = "fixed-stack")]
+    pub stack: [u8; FIXED_STACK],
+  }
+
+  /* see `efjsonLoc__` */
+  pub const LOC_ROOT_START: u8 = 0;
+  pub const LOC_KEY_FIRST_START: u8 = 1;
+  pub const LOC_KEY_START: u8 = 2;
+  pub const LOC_VALUE_START: u8 = 3;
+  pub const LOC_ELEMENT_FIRST_START: u8 = 4;
+  pub const LOC_ELEMENT_START: u8 = 5;
+  pub const LOC_ROOT_END: u8 = 6;
+  pub const LOC_KEY_END: u8 = 7;
+  pub const LOC_VALUE_END: u8 = 8;
+  pub const LOC_ELEMENT_END: u8 = 9;
+  pub const LOC_EOF: u8 = 10;
+
+  /* see `efjsonVal__`, the states `*_NEXT` exist only if escaped surrogates are combined */
+  const COMBINE: u8 = cfg!(not(feature = "no-combine-surrogate")) as u8;
+  pub const VAL_EMPTY: u8 = 0;
+  pub const VAL_NULL: u8 = 1;
+  pub const VAL_TRUE: u8 = 2;
+  pub const VAL_FALSE: u8 = 3;
+  pub const VAL_STRING: u8 = 4;
+  pub const VAL_STRING_UNICODE: u8 = 6;
+  #[cfg(not(feature = "no-combine-surrogate"))]
+  pub const VAL_STRING_UNICODE_NEXT: u8 = 7;
+  pub const VAL_NUMBER: u8 = 7 + COMBINE;
+  pub const VAL_NUMBER_FRACTION: u8 = 8 + COMBINE;
+  pub const VAL_NUMBER_EXPONENT: u8 = 9 + COMBINE;
+  pub const VAL_STRING_ESCAPE_HEX: u8 = 11 + COMBINE;
+  pub const VAL_NUMBER_INFINITY: u8 = 12 + COMBINE;
+  pub const VAL_NUMBER_NAN: u8 = 13 + COMBINE;
+  pub const VAL_NUMBER_HEX: u8 = 14 + COMBINE;
+  pub const VAL_NUMBER_BIN: u8 = 16 + COMBINE;
+  pub const VAL_COMMENT_MAY_START: u8 = 17 + COMBINE;
+  pub const VAL_MULTI_LINE_COMMENT_MAY_END: u8 = 20 + COMBINE;
+  pub const VAL_IDENTIFIER_ESCAPE: u8 = 22 + COMBINE;
+  #[cfg(not(feature = "no-combine-surrogate"))]
+  pub const VAL_IDENTIFIER_ESCAPE_NEXT: u8 = 24;
+  /** the last state */
+  pub const VAL_LAST: u8 = 22 + COMBINE * 2;
+
+  /* see `efjsonFlag__` */
+  pub const FLAG_MEET_CR: u8 = 1;
+  pub const FLAG_SINGLE_QUOTE: u8 = 2;
+  pub const FLAG_PYTHON_NONE: u8 = 4;
+  pub const FLAG_TRAILING_TEXT: u8 = 8;
+
+  /** The fields of `RawStreamParser` except the stack buffer */
+  #[derive(Debug, Clone, Copy)]
+  pub struct RawSnapshot {
+    pub position: Position,
+    pub line: Position,
+    pub column: Position,
+    pub option: u32,
+    pub location: u8,
+    pub state: u8,
+    pub flag: u8,
+    pub substate: u8,
+    pub escape: u16,
+    #[cfg(not(feature = "no-combine-surrogate"))]
+    pub prev_pair: u16,
+    pub len: StackLength,
+  }
+  impl RawStreamParser {
+    pub fn snapshot(&self) -> RawSnapshot {
+      RawSnapshot {
+        position: self.position,
+        line: self.line,
+        column: self.column,
+        option: self.option,
+        location: self.location,
+        state: self.state,
+        flag: self.flag,
+        substate: self.substate,
+        escape: self.escape,
+        #[cfg(not(feature = "no-combine-surrogate"))]
+        prev_pair: self.prev_pair,
+        len: self.len,
+      }
+    }
+    /**
+    Restore the fields saved by `snapshot`.
+
+    The stack below `len` must not have been modified since then.
+    */
+    pub fn restore(&mut self, snapshot: &RawSnapshot) {
+      self.position = snapshot.position;
+      self.line = snapshot.line;
+      self.column = snapshot.column;
+      self.option = snapshot.option;
+      self.location = snapshot.location;
+      self.state = snapshot.state;
+      self.flag = snapshot.flag;
+      self.substate = snapshot.substate;
+      self.escape = snapshot.escape;
+      #[cfg(not(feature = "no-combine-surrogate"))]
+      {
+        self.prev_pair = snapshot.prev_pair;
+      }
+      self.len = snapshot.len;
+    }
+
+    /** Get the first half of an escaped surrogate pair (0 if they are not combined) */
+    pub fn get_prev_pair(&self) -> u16 {
+      #[cfg(not(feature = "no-combine-surrogate"))]
+      return self.prev_pair;
+      #[cfg(feature = "no-combine-surrogate")]
+      0
+    }
+    pub fn set_prev_pair(&mut self, _prev_pair: u16) {
+      #[cfg(not(feature = "no-combine-surrogate"))]
+      {
+        self.prev_pair = _prev_pair;
+      }
+    }
+    /** Get the capacity of the stack in bytes */
+    pub fn get_capacity(&self) -> usize {
+      #[cfg(not(feature = "fixed-stack"))]
+      return self.cap as usize;
+      #[cfg(feature = "fixed-stack")]
+      FIXED_STACK
+    }
+    /** Get the bytes of the stack in use (`len` bits) */
+    pub fn get_stack(&self) -> &[u8] {
+      self.get_stack_prefix((self.len as usize).div_ceil(8))
+    }
+    fn get_stack_prefix(&self, size: usize) -> &[u8] {
+      #[cfg(not(feature = "fixed-stack"))]
+      return if size == 0 { &[] } else { unsafe { std::slice::from_raw_parts(self.stack, size) } };
+      #[cfg(feature = "fixed-stack")]
+      &self.stack[..size]
+    }
+    /** Get the first `size` bytes of the stack, `size` must not exceed the capacity */
+    pub fn get_stack_mut(&mut self, size: usize) -> &mut [u8] {
+      assert!(size <= self.get_capacity());
+      #[cfg(not(feature = "fixed-stack"))]
+      return if size == 0 {
+        &mut []
+      } else {
+        unsafe { std::slice::from_raw_parts_mut(self.stack, size) }
+      };
+      #[cfg(feature = "fixed-stack")]
+      &mut self.stack[..size]
+    }
+  }
+
+  /** The C UTF-8 decoder, see `efjsonUtf8Decoder` */
+  #[repr(C)]
+  #[derive(Debug, Clone, Copy, Default)]
+  pub struct Utf8Decoder {
+    pub code: u32,
+    pub rest: u16,
+    pub total: u16,
+  }
+
+  /** The C UTF-16 decoder, see `efjsonUtf16Decoder` */
+  #[repr(C)]
+  #[derive(Debug, Clone, Copy, Default)]
+  pub struct Utf16Decoder {
+    pub first: u16,
+  }
+}
+
+use outer::*;
+
+use crate::ParserOption;
+use std::sync::Arc;
+
+/**
+The error kind of the parser.
+
+It's not commended to use this enum directly, instead use the `stringify` method to get a human-readable string.
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum ErrorKind {
+  AllocFailed = 1,
+  TooManyRecursions,
+  PositionOverflow,
+  InvalidInputUtf,
+  InvalidEscapedUtf,
+  IncompleteSurrogatePair,
+  /* << other >> */
+  Eof = 0x80,
+  NonwhitespaceAfterEnd,
+  ContentAfterEof,
+  TrailingCommaForbidden,
+  Unexpected,
+  WrongBracket,
+  WrongColon,
+  EmptyValue,
+  /* << array >> */
+  CommaInEmptyArray,
+  /* << object >> */
+  BadIdentifierEscape,
+  BadPropertyNameInObject,
+  CommaInEmptyObject,
+  EmptyValueInObject,
+  ExpectedColon,
+  InvalidIdentifier,
+  InvalidIdentifierEscape,
+  RepeatedColon,
+  /* << string >> */
+  BadEscapeInString,
+  BadHexEscapeInString,
+  BadUnicodeEscapeInString,
+  ControlCharacterForbiddenInString,
+  SingleQuoteForbidden,
+  /* << number >> */
+  EmptyExponentPart,
+  EmptyFractionPart,
+  EmptyIntegerPart,
+  ExponentNotAllowed,
+  FractionNotAllowed,
+  LeadingZeroForbidden,
+  PositiveSignForbidden,
+  UnexpectedInNumber,
+  LoneDecimalPoint,
+  /* << comment >> */
+  CommentForbidden,
+  CommentNotClosed,
+  /* << limits >> (reported by the Rust side, see `Limits`) */
+  InputTooLong = 0x40,
+  StringTooLong,
+  NumberTooLong,
+  TooManyElements,
+  TooManyNodes,
+  /* << I-JSON >> (reported by the Rust side, see `StreamParser::enable_ijson`) */
+  DuplicateKey = 0x50,
+  NumberOutOfRange,
+  NoncharacterForbidden,
+  /* << feeding >> (reported by the Rust side, see `StreamParser::feed_one`) */
+  MultipleTokens = 0x60,
+}
+impl ErrorKind {
+  pub fn stringify(self) -> &'static str {
+    match self {
+      ErrorKind::InputTooLong => "<input too long>",
+      ErrorKind::StringTooLong => "<string too long>",
+      ErrorKind::NumberTooLong => "<number too long>",
+      ErrorKind::TooManyElements => "<too many elements>",
+      ErrorKind::TooManyNodes => "<too many nodes>",
+      ErrorKind::DuplicateKey => "<duplicate key>",
+      ErrorKind::NumberOutOfRange => "<number out of range>",
+      ErrorKind::NoncharacterForbidden => "<noncharacter forbidden>",
+      ErrorKind::MultipleTokens => "<multiple tokens per character>",
+      _ => unsafe { from_c_str(efjson_stringifyError(self as u8)) },
+    }
+  }
+  /** Whether the error is caused by exceeding a limit (see `Limits`) */
+  pub fn is_limit_exceeded(self) -> bool {
+    matches!(
+      self,
+      ErrorKind::TooManyRecursions
+        | ErrorKind::InputTooLong
+        | ErrorKind::StringTooLong
+        | ErrorKind::NumberTooLong
+        | ErrorKind::TooManyElements
+        | ErrorKind::TooManyNodes
+    )
+  }
+  /** Whether the error is reported by the checks of I-JSON (see `StreamParser::enable_ijson`) */
+  pub fn is_ijson_violation(self) -> bool {
+    matches!(
+      self,
+      ErrorKind::DuplicateKey | ErrorKind::NumberOutOfRange | ErrorKind::NoncharacterForbidden
+    )
+  }
+}
+
+/**
+# Safety
+`ptr` must be a static string returned by the C library.
+*/
+unsafe fn from_c_str(ptr: *const std::ffi::c_char) -> &'static str {
+  std::ffi::CStr::from_ptr(ptr).to_str().unwrap_unchecked()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Category {
+  // Error = 0,
+  Whitespace = 1,
+  Eof,
+  Null,
+  Boolean,
+  String,
+  Number,
+  Object,
+  Array,
+  Identifier,
+  Comment,
+}
+impl Category {
+  /** Get the name of the category (for example, `"string"`) */
+  pub fn as_str(self) -> &'static str {
+    unsafe { from_c_str(efjson_stringifyCategory(self as u8)) }
+  }
+}
+impl std::fmt::Display for Category {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.write_str(self.as_str())
+  }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Location {
+  Root = 0,
+  Key,
+  Value,
+  Element,
+  Array,
+  Object,
+}
+impl Location {
+  /** Get the name of the location (for example, `"element"`) */
+  pub fn as_str(self) -> &'static str {
+    unsafe { from_c_str(efjson_stringifyLocation(self as u8)) }
+  }
+}
+impl std::fmt::Display for Location {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.write_str(self.as_str())
+  }
+}
+
+/**
+An error of the parser, located at the character which causes it.
+
+# Note
+The line and columns are those of the character, which differ from `StreamParser::get_line` after a `'\r'`:
+the parser moves to the next line only when the following character is known,
+while a character after a lone `'\r'` is reported at the start of the next line.
+A tab is counted as one column.
+
+# Example
+```rust
+use efjson::{stream_parser::StreamParser, ParserOption};
+let mut parser = StreamParser::new(ParserOption::empty());
+parser.feed("[1,\r").unwrap();
+let err = parser.feed_one(']').unwrap_err();
+assert_eq!((err.position, err.line, err.column), (4, 1, 0));
+assert_eq!((parser.get_line(), parser.get_column()), (0, 4));
+```
+*/
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StreamError {
+  pub position: usize,
+  pub line: usize,
+  pub column: usize,
+  /** the offset in bytes of the input (UTF-8 for `&str` input) */
+  pub byte_position: usize,
+  /** the column in UTF-16 code units */
+  pub utf16_column: usize,
+  pub character: char,
+  pub kind: ErrorKind,
+}
+impl std::fmt::Display for StreamError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(
+      f,
+      "at {}:{}({}), character {} - {}",
+      self.line,
+      self.column,
+      self.position,
+      self.character,
+      self.kind.stringify(),
+    )
+  }
+}
+impl From<StreamError> for Box<dyn std::error::Error + Send + Sync> {
+  fn from(err: StreamError) -> Self {
+    format!("{}", err).into()
+  }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(i8)]
+pub enum Stage {
+  NotStarted = -1,
+  Parsing = 0,
+  Ended = 1,
+}
+impl Stage {
+  /** Get the name of the stage (for example, `"not_started"`) */
+  pub fn as_str(self) -> &'static str {
+    match self {
+      Stage::NotStarted => "not_started",
+      Stage::Parsing => "parsing",
+      Stage::Ended => "ended",
+    }
+  }
+}
+impl std::fmt::Display for Stage {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.write_str(self.as_str())
+  }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8, C)]
+#[allow(clippy::identity_op)]
+pub enum TokenInfo {
+  Whitespace = 1 << 4 | 0x0,
+  Eof = 2 << 4 | 0x0,
+  Null(u8, bool) = 3 << 4 | 0x0,
+
+  False(u8, bool) = 4 << 4 | 0x0,
+  True(u8, bool) = 4 << 4 | 0x1,
+
+  StringStart = 5 << 4 | 0x0,
+  StringEnd = 5 << 4 | 0x1,
+  StringNormal = 5 << 4 | 0x2,
+  StringEscapeStart = 5 << 4 | 0x3,
+  StringEscape(char) = 5 << 4 | 0x4,
+  StringEscapeUnicodeStart = 5 << 4 | 0x5,
+  /**
+  A character of `\uXXXX` in a string, the escaped character is given with the last hex digit.
+
+  The second escape of a surrogate pair continues the index from 4, as `IdentifierEscape`.
+
+  # Example
+  ```rust
+  use efjson::{stream_parser::{ErrorKind, StreamParser, TokenInfo}, ParserOption};
+  #[cfg(not(feature = "no-combine-surrogate"))]
+  {
+    let tokens = StreamParser::parse(ParserOption::empty(), r#""\uD83D\uDE00""#).unwrap();
+    assert_eq!(tokens[12].info, TokenInfo::StringEscapeUnicode(9, Some('\u{1F600}')));
+    /* the second escape is not a low surrogate */
+    let err = StreamParser::parse(ParserOption::empty(), r#""\uD800\u0041""#).unwrap_err();
+    assert_eq!((err.kind, err.position), (ErrorKind::IncompleteSurrogatePair, 12));
+  }
+  ```
+  */
+  StringEscapeUnicode(u8, Option<char>) = 5 << 4 | 0x6,
+  StringNextLine = 5 << 4 | 0x7,
+  StringEscapeHexStart = 5 << 4 | 0x8,
+  StringEscapeHex(u8, Option<char>) = 5 << 4 | 0x9,
+  /**
+  A lone escaped surrogate, with `ParserOption::LONE_SURROGATE`.
+
+  A low surrogate is reported at its last hexadecimal digit.
+  A high surrogate is reported once the character after it breaks the pair,
+  and the token shares that character, which then gets its own token (see `StreamParser::feed_one_into`).
+
+  # Example
+  ```rust
+  use efjson::{stream_parser::{ErrorKind, StreamParser, TokenInfo}, ParserOption};
+  let option = ParserOption::LONE_SURROGATE;
+  let tokens = StreamParser::parse(option, r#""\uDE00""#).unwrap();
+  assert_eq!(tokens[6].info, TokenInfo::StringEscapeSurrogate(0xDE00));
+  #[cfg(not(feature = "no-combine-surrogate"))]
+  {
+    /* a pair is still combined */
+    let tokens = StreamParser::parse(option, r#""\uD83D\uDE00""#).unwrap();
+    assert_eq!(tokens[12].info, TokenInfo::StringEscapeUnicode(9, Some('\u{1F600}')));
+    let tokens = StreamParser::parse(option, r#""\uD800x""#).unwrap();
+    let infos: Vec<_> = tokens[7..9].iter().map(|token| (token.c, token.info)).collect();
+    assert_eq!(infos, [('x', TokenInfo::StringEscapeSurrogate(0xD800)), ('x', TokenInfo::StringNormal)]);
+    /* the pair is broken at the last digit of the second escape */
+    let tokens = StreamParser::parse(option, r#""\uD800\u0041""#).unwrap();
+    assert_eq!(tokens[12].info, TokenInfo::StringEscapeSurrogate(0xD800));
+    assert_eq!(tokens[13].info, TokenInfo::StringEscapeUnicode(3, Some('A')));
+    let tokens = StreamParser::parse(option, r#""\uD800""#).unwrap();
+    assert_eq!(tokens[7].info, TokenInfo::StringEscapeSurrogate(0xD800));
+    assert_eq!((tokens[8].c, tokens[8].info), ('"', TokenInfo::StringEnd));
+    /* the character after a lone high surrogate produces two tokens */
+    let mut parser = StreamParser::new(option);
+    for c in r#""\uD800"#.chars() {
+      parser.feed_one(c).unwrap();
+    }
+    let err = parser.feed_one('"').unwrap_err();
+    assert_eq!(err.kind, ErrorKind::MultipleTokens);
+  }
+  ```
+  */
+  StringEscapeSurrogate(u16) = 5 << 4 | 0xA,
+
+  NumberIntegerDigit = 6 << 4 | 0x0,
+  NumberFractionDigit = 6 << 4 | 0x1,
+  NumberExponentDigit = 6 << 4 | 0x2,
+  NumberIntegerSign = 6 << 4 | 0x3,
+  NumberExponentSign = 6 << 4 | 0x4,
+  NumberFractionStart = 6 << 4 | 0x5,
+  NumberExponentStart = 6 << 4 | 0x6,
+  NumberNan(u8, bool) = 6 << 4 | 0x7,
+  NumberInfinity(u8, bool) = 6 << 4 | 0x8,
+  NumberHexStart = 6 << 4 | 0x9,
+  NumberHex = 6 << 4 | 0xA,
+  NumberOctStart = 6 << 4 | 0xB,
+  NumberOct = 6 << 4 | 0xC,
+  NumberBinStart = 6 << 4 | 0xD,
+  NumberBin = 6 << 4 | 0xE,
+
+  ObjectStart = 7 << 4 | 0x0,
+  ObjectNext = 7 << 4 | 0x1,
+  ObjectValueStart = 7 << 4 | 0x2,
+  ObjectEnd = 7 << 4 | 0x3,
+
+  ArrayStart = 8 << 4 | 0x0,
+  ArrayNext = 8 << 4 | 0x1,
+  ArrayEnd = 8 << 4 | 0x2,
+
+  IdentifierNormal = 9 << 4 | 0x0,
+  IdentifierEscapeStart(u8, bool) = 9 << 4 | 0x1,
+  /**
+  A character of `\uXXXX` in an identifier, the escaped character is given with the last hex digit.
+
+  The second escape of a surrogate pair continues the index from 4
+  (unless the `no-combine-surrogate` feature is enabled).
+
+  # Example
+  ```rust
+  use efjson::{stream_parser::{ErrorKind, StreamParser, TokenInfo}, ParserOption};
+  let tokens = StreamParser::parse(ParserOption::all(), r"{\u0061:1}").unwrap();
+  assert_eq!(tokens[6].info, TokenInfo::IdentifierEscape(3, Some('a')));
+  #[cfg(not(feature = "no-combine-surrogate"))]
+  {
+    let tokens = StreamParser::parse(ParserOption::all(), r"{\uD83D\uDE00:1}").unwrap();
+    assert_eq!(tokens[8].info, TokenInfo::IdentifierEscape(5, None));
+    assert_eq!(tokens[12].info, TokenInfo::IdentifierEscape(9, Some('\u{1F600}')));
+    let err = StreamParser::parse(ParserOption::all(), r"{\uD83D\u00zz:1}").unwrap_err();
+    assert_eq!(err.kind, ErrorKind::InvalidIdentifierEscape);
+    let err = StreamParser::parse(ParserOption::all(), r"{\uD83D\u0041:1}").unwrap_err();
+    assert_eq!((err.kind, err.position), (ErrorKind::IncompleteSurrogatePair, 12));
+  }
+  ```
+  */
+  IdentifierEscape(u8, Option<char>) = 9 << 4 | 0x2,
+
+  CommentMayStart = 10 << 4 | 0x0,
+  CommentSingleLine = 10 << 4 | 0x1,
+  CommentMultiLine = 10 << 4 | 0x3,
+  CommentMultiLineEnd = 10 << 4 | 0x4,
+}
+impl TokenInfo {
+  fn get_type(&self) -> u8 {
+    unsafe { *(self as *const TokenInfo as *const u8) }
+  }
+  pub fn get_category(&self) -> Category {
+    unsafe { std::mem::transmute::<u8, Category>(self.get_type() >> 4) }
+  }
+  /**
+  Get the name of the token type, prefixed by its category (for example, `"[string]escape"`).
+
+  The payload of the token is not included.
+
+  # Example
+  ```rust
+  use efjson::stream_parser::TokenInfo;
+  assert_eq!(TokenInfo::CommentSingleLine.as_str(), "[comment]single_line");
+  assert_eq!(TokenInfo::CommentMultiLine.as_str(), "[comment]multi_line");
+  assert_eq!(TokenInfo::CommentMultiLineEnd.to_string(), "[comment]multi_line_end");
+  ```
+  */
+  pub fn as_str(&self) -> &'static str {
+    unsafe { from_c_str(efjson_stringifyType(self.get_type() as u16)) }
+  }
+}
+impl std::fmt::Display for TokenInfo {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.write_str(self.as_str())
+  }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Token {
+  pub c: char,
+  pub info: TokenInfo,
+}
+impl Token {
+  pub fn is_space(&self) -> bool {
+    matches!(
+      self.info,
+      TokenInfo::Whitespace
+        | TokenInfo::CommentMayStart
+        | TokenInfo::CommentMultiLine
+        | TokenInfo::CommentSingleLine
+        | TokenInfo::CommentMultiLineEnd
+        | TokenInfo::Eof
+    )
+  }
+}
+
+/**
+# Safety
+`ctoken` must be an error token returned by the C parser.
+*/
+unsafe fn convert_error(ctoken: outer::Token) -> ErrorKind {
+  std::mem::transmute::<u8, ErrorKind>(ctoken.extra as u8)
+}
+/**
+# Safety
+`ctoken` must be a non-error token returned by the C parser.
+*/
+unsafe fn convert_token(ctoken: outer::Token) -> TokenInfo {
+  match ctoken.r#type {
+    0x30 => TokenInfo::Null(ctoken.index, ctoken.done != 0),
+    0x40 => TokenInfo::False(ctoken.index, ctoken.done != 0),
+    0x41 => TokenInfo::True(ctoken.index, ctoken.done != 0),
+    0x54 => TokenInfo::StringEscape(char::from_u32_unchecked(ctoken.extra)),
+    0x56 => TokenInfo::StringEscapeUnicode(
+      ctoken.index,
+      if ctoken.done != 0 { Some(char::from_u32_unchecked(ctoken.extra)) } else { None },
+    ),
+    0x59 => TokenInfo::StringEscapeHex(
+      ctoken.index,
+      if ctoken.done != 0 { Some(char::from_u32_unchecked(ctoken.extra)) } else { None },
+    ),
+    0x5A => TokenInfo::StringEscapeSurrogate(ctoken.extra as u16),
+    0x67 => TokenInfo::NumberNan(ctoken.index, ctoken.done != 0),
+    0x68 => TokenInfo::NumberInfinity(ctoken.index, ctoken.done != 0),
+    0x91 => TokenInfo::IdentifierEscapeStart(ctoken.index, ctoken.done != 0),
+    0x92 => TokenInfo::IdentifierEscape(
+      ctoken.index,
+      if ctoken.done != 0 { Some(char::from_u32_unchecked(ctoken.extra)) } else { None },
+    ),
+    typ => {
+      let mut info = std::mem::MaybeUninit::zeroed();
+      (info.as_mut_ptr() as *mut u8).write(typ);
+      info.assume_init()
+    }
+  }
+}
+
+/** The number of characters passed to the C parser at once */
+const FEED_BLOCK_SIZE: usize = 256;
+
+/**
+A state machine capable of parsing JSON data one code point at a time and outputting Token information.
+
+Note: The underlying implementation of this class is in C, and all unsafe parts have been encapsulated.
+
+# Example
+A cloned parser owns a copy of the nesting stack, and continues independently.
+```rust
+use efjson::{stream_parser::StreamParser, ParserOption};
+let mut parser = StreamParser::new(ParserOption::empty());
+parser.feed(r#"[{"a":[{"b":[{"c":["#).unwrap();
+let mut cloned = parser.clone();
+cloned.feed("1]}]}]}]").unwrap();
+parser.feed("2]}]}]}]").unwrap();
+cloned.feed_one('\0').unwrap();
+parser.feed_one('\0').unwrap();
+```
+*/
+#[derive(Debug, Clone)]
+pub struct StreamParser {
+  raw: RawStreamParser,
+  utf8: Utf8Decoder,
+  byte_position: usize,
+  /** the number of supplementary characters on line `astral_line`, see `get_utf16_column` */
+  astral: usize,
+  astral_line: usize,
+  limits: Option<Box<Limiter>>,
+  /** shared with checkpoints, and copied on write (see `checkpoint`) */
+  path: Option<Arc<PathTracker>>,
+  ijson: Option<Arc<IJsonChecker>>,
+  /** a copy of the stack shared with checkpoints, see `checkpoint` */
+  image: Option<Arc<[u8]>>,
+  /** the number of bits at the bottom of the stack which are still the same as `image` */
+  image_len: StackLength,
+  /** the mistakes repaired, see `get_warnings` */
+  warnings: Vec<RepairWarning>,
+}
+
+impl StreamParser {
+  /** Get the current line number (starting from 0) */
+  pub fn get_line(&self) -> usize {
+    unsafe { efjsonStreamParser_getLine(&self.raw) as usize }
+  }
+  /** Get the current column number (starting from 0), a tab is counted as one column */
+  pub fn get_column(&self) -> usize {
+    unsafe { efjsonStreamParser_getColumn(&self.raw) as usize }
+  }
+  /** Get the current position number (starting from 0) */
+  pub fn get_position(&self) -> usize {
+    unsafe { efjsonStreamParser_getPosition(&self.raw) as usize }
+  }
+  /** Get the current offset in bytes (starting from 0) */
+  pub fn get_byte_position(&self) -> usize {
+    self.byte_position
+  }
+  /** Get the current column number in UTF-16 code units (starting from 0) */
+  pub fn get_utf16_column(&self) -> usize {
+    let column = self.get_column();
+    if self.astral_line == self.raw.line {
+      column + self.astral
+    } else {
+      column
+    }
+  }
+  /** Get the current location */
+  pub fn get_location(&self) -> Location {
+    unsafe { efjsonStreamParser_getLocation(&self.raw) }
+  }
+  /**
+  Get the current stage
+
+  # Example
+  ```rust
+  use efjson::{stream_parser::{Stage, StreamParser}, ParserOption};
+  let mut parser = StreamParser::new(ParserOption::empty());
+  assert_eq!(parser.get_stage(), Stage::NotStarted);
+  parser.feed("[1").unwrap();
+  assert_eq!(parser.get_stage(), Stage::Parsing);
+  parser.feed("]").unwrap();
+  assert_eq!(parser.get_stage(), Stage::Ended);
+  ```
+  */
+  pub fn get_stage(&self) -> Stage {
+    match unsafe { efjsonStreamParser_getStage(&self.raw) } {
+      -1 => Stage::NotStarted,
+      0 => Stage::Parsing,
+      1 => Stage::Ended,
+      _ => unsafe { std::hint::unreachable_unchecked() },
+    }
+  }
+
+  fn make_error(&self, c: char, kind: ErrorKind) -> StreamError {
+    let position = self.get_text_position_of(c);
+    StreamError {
+      character: c,
+      position: position.position,
+      line: position.line,
+      column: position.column,
+      byte_position: position.byte_position,
+      utf16_column: position.utf16_column,
+      kind,
+    }
+  }
+}
+impl StreamParser {
+  pub fn new(option: ParserOption) -> Self {
+    let mut parser = std::mem::MaybeUninit::<RawStreamParser>::uninit();
+    let raw = unsafe {
+      efjsonStreamParser_init(parser.as_mut_ptr(), option.bits());
+      parser.assume_init()
+    };
+    Self {
+      raw,
+      utf8: Utf8Decoder::default(),
+      byte_position: 0,
+      astral: 0,
+      astral_line: 0,
+      limits: None,
+      path: None,
+      ijson: None,
+      image: None,
+      image_len: 0,
+      warnings: Vec::new(),
+    }
+  }
+
+  /**
+  Feed a single character to the parser and return the next token.
+
+  # Note
+  If the string is ended, you need to explicitly pass `'\0'` to notify the parser.
+
+  # Example
+  ```rust
+  use efjson::{stream_parser::StreamParser,ParserOption};
+  let mut parser = StreamParser::new(ParserOption::all());
+  println!("{:?}", parser.feed_one('n'));
+  ```
+
+  # Panics
+  The function does not panic.
+
+  # Errors
+  If the character is invalid or the parser encounters an error, it will return a `StreamError`.
+  And the state machine will remain unchanged. You can choose to ignore this error and continue parsing,
+  but note that this may lead to incorrect parsing results
+  (see `RecoveringStreamParser` for a parser which recovers from errors).
+
+  If a UTF-8 sequence fed by `feed_byte` is incomplete, it is discarded and reported as `ErrorKind::InvalidInputUtf`,
+  the character is not fed in this case.
+
+  With `ParserOption::MISSING_COMMA` or `ParserOption::LONE_SURROGATE`, a character may produce two tokens
+  (see `feed_one_into`), such a character is rejected with `ErrorKind::MultipleTokens` and not fed,
+  feed it with `feed_one_into` instead.
+
+  ```rust
+  use efjson::{stream_parser::{ErrorKind, StreamParser}, ParserOption};
+  let mut parser = StreamParser::new(ParserOption::MISSING_COMMA);
+  for c in "[1 ".chars() {
+    parser.feed_one(c).unwrap();
+  }
+  /* a comma is inserted before `2` */
+  assert_eq!(parser.feed_one('2').unwrap_err().kind, ErrorKind::MultipleTokens);
+  assert_eq!(parser.feed_byte(b'2').unwrap_err().kind, ErrorKind::MultipleTokens);
+  let mut tokens = Vec::new();
+  for c in "2]".chars() {
+    parser.feed_one_into(c, &mut tokens).unwrap();
+  }
+  assert_eq!(tokens.len(), 3);
+  assert!(parser.feed_one('\0').is_ok());
+  ```
+   */
+  pub fn feed_one(&mut self, c: char) -> Result<Token, StreamError> {
+    self.feed_pair_mode(c, false).map(|(_, token)| token)
+  }
+  /** Feed a character which takes `len` bytes in the input */
+  fn feed_sized(&mut self, c: char, len: usize) -> Result<Token, StreamError> {
+    if self.is_repairing() {
+      let (flag, position) = (self.raw.flag, self.get_text_position_of(c));
+      let token = self.feed_tracked(c, len)?;
+      self.track_repair(&token, flag, position);
+      return Ok(token);
+    }
+    self.feed_tracked(c, len)
+  }
+  /** Feed a character, and track its path */
+  fn feed_tracked(&mut self, c: char, len: usize) -> Result<Token, StreamError> {
+    let token = if self.limits.is_some() || self.ijson.is_some() {
+      self.feed_checked(c, len)
+    } else {
+      self.feed_raw(c, len)
+    }?;
+    if let Some(path) = self.path.as_mut() {
+      Arc::make_mut(path).feed(&token);
+    }
+    Ok(token)
+  }
+  /** Feed a character, and check the token by `Limits` and I-JSON */
+  fn feed_checked(&mut self, c: char, len: usize) -> Result<Token, StreamError> {
+    if let Some(limiter) = self.limits.as_ref() {
+      limiter.check_input(c, self.raw.position).map_err(|kind| self.make_error(c, kind))?;
+    }
+    let snapshot = self.raw.snapshot();
+    let token = self.feed_raw(c, len)?;
+    let depth = self.raw.len as usize;
+    let result = match self.ijson.as_ref() {
+      Some(checker) => checker.check(&token),
+      None => Ok(()),
+    }
+    .and_then(|_| match self.limits.as_mut() {
+      Some(limiter) => limiter.check(&snapshot, depth, token.info),
+      None => Ok(()),
+    });
+    if let Err(kind) = result {
+      self.unfeed(&snapshot, c, len);
+      return Err(self.make_error(c, kind));
+    }
+    if let Some(checker) = self.ijson.as_mut() {
+      Arc::make_mut(checker).update(&token);
+    }
+    Ok(token)
+  }
+  /** Undo `feed_raw` of a character which is rejected after it is fed */
+  fn unfeed(&mut self, snapshot: &RawSnapshot, c: char, len: usize) {
+    /* `'\0'` and a character fed again (see `Token::is_consumed`) do not move the position */
+    if self.raw.position != snapshot.position {
+      self.byte_position -= len;
+      if c > '\u{FFFF}' {
+        self.astral -= 1;
+      }
+    }
+    self.raw.restore(snapshot);
+  }
+  fn feed_raw(&mut self, c: char, len: usize) -> Result<Token, StreamError> {
+    let ctoken = self.feed_ctoken(c, len)?;
+    Ok(Token { c, info: unsafe { convert_token(ctoken) } })
+  }
+  /** Feed a character to the C parser, without converting the token */
+  fn feed_ctoken(&mut self, c: char, len: usize) -> Result<outer::Token, StreamError> {
+    let ctoken = unsafe { efjsonStreamParser_feedOne(&mut self.raw, c as u32) };
+    if ctoken.r#type == 0 {
+      return Err(self.make_error(c, unsafe { convert_error(ctoken) }));
+    }
+    self.image_len = self.image_len.min(self.raw.len);
+    if !ctoken.is_consumed() {
+      return Ok(ctoken);
+    }
+    if c != '\0' {
+      self.byte_position += len;
+    }
+    if c > '\u{FFFF}' {
+      self.track_astral(&[c]);
+    }
+    Ok(ctoken)
+  }
+  /** Count the supplementary characters fed just now, which are on the current line */
+  fn track_astral(&mut self, fed: &[char]) {
+    let count = |chars: &[char]| chars.iter().filter(|&&c| c > '\u{FFFF}').count();
+    if self.astral_line == self.raw.line {
+      self.astral += count(fed);
+    } else {
+      /* the last `column` characters are on the current line */
+      let column = self.raw.column.min(fed.len());
+      self.astral = count(&fed[fed.len() - column..]);
+      self.astral_line = self.raw.line;
+    }
+  }
+
+  /**
+  Feed an iterator of characters to the parser and return a vector of tokens.
+
+  # Note
+  If the string is ended, you need to explicitly pass `'\0'` to notify the parser.
+
+  # Panics
+  Panics if the number of tokens exceeds `isize::MAX`.
+
+  # Errors
+  If the character is invalid or the parser encounters an error, it will return a `StreamError`.
+  And the state machine will remain unchanged,
+  but the function will not roll back the characters that have already been fed.
+  */
+  pub fn feed_iter(&mut self, iter: impl Iterator<Item = char>) -> Result<Vec<Token>, StreamError> {
+    let mut tokens = Vec::new();
+    for c in iter {
+      self.feed_one_into(c, &mut tokens)?;
+    }
+    Ok(tokens)
+  }
+  pub fn feed(&mut self, s: &str) -> Result<Vec<Token>, StreamError> {
+    let mut tokens = Vec::new();
+    self.feed_into(s, &mut tokens)?;
+    Ok(tokens)
+  }
+
+  /**
+  Feed a string to the parser and append the tokens to `dest`.
+
+  The characters are passed to the C parser in blocks, instead of one call per character.
+
+  # Note
+  If the string is ended, you need to explicitly pass `'\0'` to notify the parser.
+
+  # Errors
+  The same as `feed_iter`, and `dest` is truncated to its original length.
+  The state machine stops at the character that caused the error, so the position of the error is exact.
+  */
+  pub fn feed_into(&mut self, s: &str, dest: &mut Vec<Token>) -> Result<(), StreamError> {
+    let origin = dest.len();
+    dest.reserve(s.len());
+    let mut block = ['\0'; FEED_BLOCK_SIZE];
+    let mut chars = s.chars();
+    loop {
+      let mut len = 0;
+      for c in chars.by_ref() {
+        block[len] = c;
+        len += 1;
+        if len == FEED_BLOCK_SIZE || c == '\0' {
+          break;
+        }
+      }
+      if len == 0 {
+        return Ok(());
+      }
+      if let Err(err) = self.feed_block(&block[..len], dest) {
+        dest.truncate(origin);
+        return Err(err);
+      }
+    }
+  }
+  /**
+  Feed a slice of characters to the parser and append the tokens to `dest`.
+
+  See `feed_into`.
+  */
+  pub fn feed_chars_into(
+    &mut self,
+    src: &[char],
+    dest: &mut Vec<Token>,
+  ) -> Result<(), StreamError> {
+    let origin = dest.len();
+    dest.reserve(src.len());
+    let mut rest = src;
+    while !rest.is_empty() {
+      let len = rest
+        .iter()
+        .take(FEED_BLOCK_SIZE)
+        .position(|&c| c == '\0')
+        .map_or(rest.len().min(FEED_BLOCK_SIZE), |i| i + 1);
+      if let Err(err) = self.feed_block(&rest[..len], dest) {
+        dest.truncate(origin);
+        return Err(err);
+      }
+      rest = &rest[len..];
+    }
+    Ok(())
+  }
+  /** `block` must not be longer than `FEED_BLOCK_SIZE`, and `'\0'` can only be its last character. */
+  fn feed_block(&mut self, block: &[char], dest: &mut Vec<Token>) -> Result<(), StreamError> {
+    if self.needs_tokens() {
+      for &c in block {
+        self.feed_one_into(c, dest)?;
+      }
+      return Ok(());
+    }
+    let mut ctokens = std::mem::MaybeUninit::<[outer::Token; FEED_BLOCK_SIZE]>::uninit();
+    let ctokens = ctokens.as_mut_ptr() as *mut outer::Token;
+    let mut block = block;
+    loop {
+      let (position, depth) = (self.get_position(), self.raw.len);
+      let n = unsafe {
+        efjsonStreamParser_feed(&mut self.raw, ctokens, block.as_ptr() as *const u32, block.len())
+      };
+      if n == 0 && unsafe { (*ctokens).r#type } == 0 {
+        // `'\0'` does not move the position, but it can only be the last one
+        let i = self.get_position() - position;
+        self.track_image(depth, unsafe { std::slice::from_raw_parts(ctokens, i) });
+        self.byte_position += block[..i].iter().map(|c| c.len_utf8()).sum::<usize>();
+        self.track_astral(&block[..i]);
+        return Err(self.make_error(block[i], unsafe { convert_error(*ctokens) }));
+      }
+      self.track_astral(&block[..n]);
+      self.track_image(depth, unsafe { std::slice::from_raw_parts(ctokens, n) });
+      for (k, &c) in block[..n].iter().enumerate() {
+        dest.push(Token { c, info: unsafe { convert_token(*ctokens.add(k)) } });
+        if c != '\0' {
+          self.byte_position += c.len_utf8();
+        }
+      }
+      if n == block.len() {
+        return Ok(());
+      }
+      /* a lone high surrogate is reported before `block[n]`, which is fed again */
+      dest.push(Token { c: block[n], info: unsafe { convert_token(*ctokens.add(n)) } });
+      block = &block[n..];
+    }
+  }
+
+  /**
+  Feed a single byte of UTF-8 input to the parser.
+
+  Returns `None` while the byte only continues an incomplete UTF-8 sequence,
+  the partial sequence is kept until the next call.
+
+  # Note
+  If the input is ended, you need to explicitly pass `'\0'` to `feed_one` to notify the parser,
+  an incomplete sequence left at this time is reported as an error.
+
+  # Errors
+  If the byte is not valid UTF-8, it will return a `StreamError` whose kind is `ErrorKind::InvalidInputUtf`,
+  and whose `byte_position` is the offset of the invalid sequence.
+  The invalid sequence (including this byte) is discarded.
+  Other errors are the same as `feed_one`.
+  A character rejected with `ErrorKind::MultipleTokens` is decoded but not fed,
+  feed `StreamError::character` with `feed_one_into` instead (or use `feed_bytes`).
+
+  # Example
+  ```rust
+  use efjson::{stream_parser::{ErrorKind, StreamParser}, ParserOption};
+  let mut parser = StreamParser::new(ParserOption::empty());
+  parser.feed_one('"').unwrap();
+  assert_eq!(parser.feed_byte(0xE4).unwrap(), None);
+  /* a character interrupting the sequence */
+  let err = parser.feed_one('"').unwrap_err();
+  assert_eq!((err.kind, err.byte_position), (ErrorKind::InvalidInputUtf, 1));
+  parser.feed_one('"').unwrap();
+  assert_eq!(parser.get_byte_position(), 3);
+  ```
+  */
+  pub fn feed_byte(&mut self, b: u8) -> Result<Option<Token>, StreamError> {
+    match self.decode_byte(b)? {
+      Some(c) => self.feed_one(c).map(Some),
+      None => Ok(None),
+    }
+  }
+  /** Decode a byte of UTF-8 input, and return the character it completes */
+  pub(crate) fn decode_byte(&mut self, b: u8) -> Result<Option<char>, StreamError> {
+    if self.utf8.rest == 0 && b < 0x80 {
+      return Ok(Some(b as char));
+    }
+    let pending =
+      if self.utf8.rest == 0 { 0 } else { (self.utf8.total - self.utf8.rest) as usize + 1 };
+    let mut code = 0u32;
+    match unsafe { efjsonUtf8Decoder_feed(&mut self.utf8, &mut code, b) } {
+      0 => Ok(None),
+      1 => match char::from_u32(code) {
+        Some(c) => Ok(Some(c)),
+        None => Err(self.discard_utf8(pending + 1)),
+      },
+      _ => Err(self.discard_utf8(pending + 1)),
+    }
+  }
+  fn discard_utf8(&mut self, len: usize) -> StreamError {
+    let err = self.make_error(char::REPLACEMENT_CHARACTER, ErrorKind::InvalidInputUtf);
+    self.byte_position += len;
+    self.utf8 = Utf8Decoder::default();
+    err
+  }
+  /**
+  Feed a chunk of UTF-8 input to the parser and return a vector of tokens.
+
+  A code point may be split across chunks, see `feed_byte`.
+
+  # Errors
+  The same as `feed_byte`, the function will not roll back the bytes that have already been fed.
+  */
+  pub fn feed_bytes(&mut self, bytes: &[u8]) -> Result<Vec<Token>, StreamError> {
+    let mut tokens = Vec::with_capacity(bytes.len());
+    for &b in bytes {
+      if let Some(c) = self.decode_byte(b)? {
+        self.feed_one_into(c, &mut tokens)?;
+      }
+    }
+    Ok(tokens)
+  }
+}
+
+impl StreamParser {
+  pub fn parse(option: ParserOption, s: &str) -> Result<Vec<Token>, StreamError> {
+    let mut parser = StreamParser::new(option);
+    let mut tokens = parser.feed(s)?;
+    tokens.push(parser.feed_one('\0')?);
+    Ok(tokens)
+  }
+  pub fn parse_iter(
+    option: ParserOption,
+    iter: impl Iterator<Item = char>,
+  ) -> Result<Vec<Token>, StreamError> {
+    let mut parser = StreamParser::new(option);
+    let mut tokens = parser.feed_iter(iter)?;
+    tokens.push(parser.feed_one('\0')?);
+    Ok(tokens)
+  }
+  pub fn parse_bytes(option: ParserOption, bytes: &[u8]) -> Result<Vec<Token>, StreamError> {
+    let mut parser = StreamParser::new(option);
+    let mut tokens = parser.feed_bytes(bytes)?;
+    tokens.push(parser.feed_one('\0')?);
+    Ok(tokens)
+  }
+  pub fn create_iter(
+    option: ParserOption,
+    iter: impl Iterator<Item = char>,
+  ) -> impl Iterator<Item = Result<Token, StreamError>> {
+    let mut parser = StreamParser::new(option);
+    iter.chain(std::iter::once('\0')).flat_map(move |c| parser.feed_pair_iter(c)).flatten()
+  }
+  pub fn create_bytes_iter(
+    option: ParserOption,
+    iter: impl Iterator<Item = u8>,
+  ) -> impl Iterator<Item = Result<Token, StreamError>> {
+    let mut parser = StreamParser::new(option);
+    iter
+      .map(Some)
+      .chain(std::iter::once(None))
+      .filter_map(move |b| match b {
+        Some(b) => match parser.decode_byte(b) {
+          Ok(Some(c)) => Some(parser.feed_pair_iter(c)),
+          Ok(None) => None,
+          Err(err) => Some([Some(Err(err)), None]),
+        },
+        None => Some(parser.feed_pair_iter('\0')),
+      })
+      .flatten()
+      .flatten()
+  }
+  /** `feed_pair` for the iterators of tokens */
+  pub(crate) fn feed_pair_iter(&mut self, c: char) -> [Option<Result<Token, StreamError>>; 2] {
+    match self.feed_pair(c) {
+      Ok((comma, token)) => [comma.map(Ok), Some(Ok(token))],
+      Err(err) => [Some(Err(err)), None],
+    }
+  }
+}
+
+#[cfg(not(feature = "fixed-stack"))]
+impl Drop for RawStreamParser {
+  fn drop(&mut self) {
+    unsafe { efjsonStreamParser_deinit(self) }
+  }
+}
+#[cfg(not(feature = "fixed-stack"))]
+impl Clone for RawStreamParser {
+  fn clone(&self) -> Self {
+    let mut parser = std::mem::MaybeUninit::<RawStreamParser>::uninit();
+    unsafe {
+      if efjsonStreamParser_initCopy(parser.as_mut_ptr(), self) != 0 {
+        std::alloc::handle_alloc_error(std::alloc::Layout::array::<u8>(self.cap as usize).unwrap());
+      }
+      parser.assume_init()
+    }
+  }
+}
+unsafe impl Send for RawStreamParser {}
+unsafe impl Sync for RawStreamParser {}
+
+mod encoding;
+pub use encoding::*;
+mod reader;
+pub use reader::*;
+mod limits;
+pub use limits::*;
+mod recovery;
+pub use recovery::*;
+mod position;
+pub use position::*;
+mod multi;
+pub use multi::*;
+mod sequence;
+pub use sequence::*;
+mod state;
+pub use state::*;
+mod checkpoint;
+pub use checkpoint::*;
+mod path;
+pub use path::*;
+mod ijson;
+use ijson::*;
+mod repair;
+pub use repair::*;
+mod expected;
+pub use expected::*;
+mod validate;
+pub use validate::*;
+mod coarse;
+pub use coarse::*;
+mod sink;
+pub use sink::*;
+mod tape;
+pub use tape::*;
@@ -0,0 +1,211 @@
+use super::outer::*;
+use super::{is_next_line, ErrorKind, Limits, Location, Stage, StreamError, StreamParser, Token};
+use crate::ParserOption;
+use std::sync::Arc;
+
+impl StreamParser {
+  /** Start a new root value, the position remains unchanged */
+  pub(super) fn reset_document(&mut self) {
+    let raw = &mut self.raw;
+    raw.location = LOC_ROOT_START;
+    raw.state = VAL_EMPTY;
+    raw.flag &= FLAG_MEET_CR;
+    raw.substate = 0;
+    raw.escape = 0;
+    raw.set_prev_pair(0);
+    raw.len = 0;
+    self.image_len = 0;
+    if let Some(limiter) = self.limits.as_mut() {
+      limiter.reset_document();
+    }
+    if let Some(path) = self.path.as_mut() {
+      Arc::make_mut(path).clear();
+    }
+    if let Some(checker) = self.ijson.as_mut() {
+      Arc::make_mut(checker).clear();
+    }
+  }
+}
+
+/** A token with the index of the document it belongs to */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DocumentToken {
+  /** the index of the document (starting from 0) */
+  pub document: usize,
+  pub token: Token,
+}
+
+/**
+A layer over `StreamParser` accepting a sequence of JSON values,
+such as newline-delimited JSON (NDJSON, JSON Lines) or concatenated JSON.
+
+The parser starts a new document once a root value ends and another value begins.
+Whitespace (and comments) between documents belongs to the previous document.
+
+# Errors
+An error does not stop the parser: the rest of the line is skipped,
+and a new document starts after the line break.
+
+# Note
+`Limits` (except `max_input_length`) are applied to each document.
+`ParserOption::TRAILING_TEXT` is ignored, since the text after a root value starts a new document.
+
+# Example
+```rust
+use efjson::{stream_parser::MultiDocumentParser, ParserOption};
+let results = MultiDocumentParser::parse(ParserOption::empty(), "{\"a\":1}\n[1,,]\n\"b\" 2");
+let errors: Vec<_> = results.iter().filter_map(|result| result.as_ref().err()).collect();
+assert_eq!((errors.len(), errors[0].line), (1, 1));
+let last = results.last().unwrap().as_ref().unwrap();
+assert_eq!(last.document, 3);
+```
+*/
+#[derive(Debug, Clone)]
+pub struct MultiDocumentParser {
+  parser: StreamParser,
+  document: usize,
+  skipping: bool,
+}
+impl From<StreamParser> for MultiDocumentParser {
+  fn from(mut parser: StreamParser) -> Self {
+    parser.raw.option &= !ParserOption::TRAILING_TEXT.bits();
+    Self { parser, document: 0, skipping: false }
+  }
+}
+
+impl MultiDocumentParser {
+  pub fn new(option: ParserOption) -> Self {
+    Self::from(StreamParser::new(option))
+  }
+  /** Create a parser with limits, see `StreamParser::with_limits` */
+  pub fn with_limits(option: ParserOption, limits: Limits) -> Self {
+    Self::from(StreamParser::with_limits(option, limits))
+  }
+
+  /** Get the index of the current document (starting from 0) */
+  pub fn get_document(&self) -> usize {
+    self.document
+  }
+  pub fn get_line(&self) -> usize {
+    self.parser.get_line()
+  }
+  pub fn get_column(&self) -> usize {
+    self.parser.get_column()
+  }
+  pub fn get_position(&self) -> usize {
+    self.parser.get_position()
+  }
+  pub fn get_byte_position(&self) -> usize {
+    self.parser.get_byte_position()
+  }
+  pub fn get_location(&self) -> Location {
+    self.parser.get_location()
+  }
+  /** Get the stage of the current document */
+  pub fn get_stage(&self) -> Stage {
+    self.parser.get_stage()
+  }
+
+  /**
+  Feed a single character to the parser and return the next token.
+
+  Returns `None` if the character is skipped after an error.
+
+  # Note
+  If the string is ended, you need to explicitly pass `'\0'` to notify the parser.
+
+  # Errors
+  The same as `StreamParser::feed_one`, but the parser can still be used
+  (a character rejected with `ErrorKind::MultipleTokens` is not fed, use `feed` instead).
+  */
+  pub fn feed_one(&mut self, c: char) -> Result<Option<DocumentToken>, StreamError> {
+    Ok(self.feed_pair(c, false)?.map(|(_, token)| token))
+  }
+  /**
+  Feed a single character, and return the comma inserted before it (if any) and its token,
+  see `StreamParser::feed_pair_mode` for `pairs`
+  */
+  fn feed_pair(
+    &mut self,
+    c: char,
+    pairs: bool,
+  ) -> Result<Option<(Option<DocumentToken>, DocumentToken)>, StreamError> {
+    if self.skipping {
+      if c != '\0' {
+        self.parser.skip_char(c);
+        if is_next_line(c) {
+          self.next_document();
+        }
+        return Ok(None);
+      }
+      self.next_document();
+    }
+    if c == '\0' && self.parser.raw.location == LOC_ROOT_START && self.parser.raw.state == VAL_EMPTY
+    {
+      /* no document is started */
+      self.parser.raw.location = LOC_ROOT_END;
+    }
+    let mut result = self.parser.feed_pair_mode(c, pairs);
+    if matches!(&result, Err(err) if err.kind == ErrorKind::NonwhitespaceAfterEnd) {
+      self.next_document();
+      result = self.parser.feed_pair_mode(c, pairs);
+    }
+    let err = match result {
+      Ok((comma, token)) => {
+        let document = self.document;
+        let comma = comma.map(|token| DocumentToken { document, token });
+        return Ok(Some((comma, DocumentToken { document, token })));
+      }
+      Err(err) => err,
+    };
+    if c != '\0' && err.kind != ErrorKind::MultipleTokens {
+      self.parser.skip_char(c);
+      if is_next_line(c) {
+        self.next_document();
+      } else {
+        self.skipping = true;
+      }
+    }
+    Err(err)
+  }
+  /** Feed a string to the parser, an error does not stop the parser */
+  pub fn feed(&mut self, s: &str) -> Vec<Result<DocumentToken, StreamError>> {
+    s.chars().flat_map(|c| self.feed_pair_iter(c)).flatten().collect()
+  }
+  /** Notify the parser that the input is ended, and return the last token (`Eof`) */
+  pub fn end(&mut self) -> Result<DocumentToken, StreamError> {
+    self.feed_one('\0').map(|token| token.expect("the end of input is never skipped"))
+  }
+
+  /** `feed_pair` for the iterators of tokens */
+  fn feed_pair_iter(&mut self, c: char) -> [Option<Result<DocumentToken, StreamError>>; 2] {
+    match self.feed_pair(c, true) {
+      Ok(Some((comma, token))) => [comma.map(Ok), Some(Ok(token))],
+      Ok(None) => [None, None],
+      Err(err) => [Some(Err(err)), None],
+    }
+  }
+
+  fn next_document(&mut self) {
+    self.parser.reset_document();
+    self.document += 1;
+    self.skipping = false;
+  }
+}
+
+impl MultiDocumentParser {
+  pub fn parse(option: ParserOption, s: &str) -> Vec<Result<DocumentToken, StreamError>> {
+    let mut parser = MultiDocumentParser::new(option);
+    let mut results = parser.feed(s);
+    results.push(parser.end());
+    results
+  }
+  /** Create an iterator of tokens, `'\0'` is fed at the end of `iter` */
+  pub fn create_iter(
+    option: ParserOption,
+    iter: impl Iterator<Item = char>,
+  ) -> impl Iterator<Item = Result<DocumentToken, StreamError>> {
+    let mut parser = MultiDocumentParser::new(option);
+    iter.chain(std::iter::once('\0')).flat_map(move |c| parser.feed_pair_iter(c)).flatten()
+  }
+}
@@ -0,0 +1,172 @@
+use super::{StreamParser, Token, TokenInfo};
+
+/** A reference token of `JsonPointer` */
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PathSegment {
+  /** the key of a member of an object (unescaped) */
+  Key(String),
+  /** the index of an element of an array */
+  Index(usize),
+}
+impl std::fmt::Display for PathSegment {
+  /** Write the segment escaped as RFC 6901 (`~` as `~0`, `/` as `~1`) */
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      PathSegment::Key(key) => {
+        for c in key.chars() {
+          match c {
+            '~' => f.write_str("~0")?,
+            '/' => f.write_str("~1")?,
+            c => std::fmt::Write::write_char(f, c)?,
+          }
+        }
+        Ok(())
+      }
+      PathSegment::Index(index) => write!(f, "{}", index),
+    }
+  }
+}
+
+/**
+A JSON Pointer (RFC 6901), see `StreamParser::current_path`.
+
+It is displayed as a string such as `/servers/3/port` (the root is an empty string).
+*/
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct JsonPointer {
+  segments: Vec<PathSegment>,
+}
+impl JsonPointer {
+  pub fn get_segments(&self) -> &[PathSegment] {
+    &self.segments
+  }
+  /** Get the number of segments */
+  pub fn len(&self) -> usize {
+    self.segments.len()
+  }
+  /** Whether the pointer refers to the root */
+  pub fn is_empty(&self) -> bool {
+    self.segments.is_empty()
+  }
+}
+impl From<Vec<PathSegment>> for JsonPointer {
+  fn from(segments: Vec<PathSegment>) -> Self {
+    Self { segments }
+  }
+}
+impl std::fmt::Display for JsonPointer {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    for segment in &self.segments {
+      write!(f, "/{}", segment)?;
+    }
+    Ok(())
+  }
+}
+
+/** Track the path from the tokens, see `StreamParser::enable_path_tracking` */
+#[derive(Debug, Clone, Default)]
+pub(super) struct PathTracker {
+  /** a segment for each container */
+  pub(super) segments: Vec<PathSegment>,
+  /** whether the key of the innermost object is started */
+  pub(super) key_started: bool,
+  /** whether the innermost object expects a key */
+  pub(super) in_key: bool,
+}
+impl PathTracker {
+  pub(super) fn clear(&mut self) {
+    self.segments.clear();
+    self.key_started = false;
+    self.in_key = false;
+  }
+  fn push_key(&mut self, c: char) {
+    if let Some(PathSegment::Key(key)) = self.segments.last_mut() {
+      key.push(c);
+    }
+  }
+  pub(super) fn feed(&mut self, token: &Token) {
+    match token.info {
+      TokenInfo::ObjectStart => {
+        self.segments.push(PathSegment::Key(String::new()));
+        (self.key_started, self.in_key) = (false, true);
+      }
+      TokenInfo::ObjectNext => {
+        if let Some(PathSegment::Key(key)) = self.segments.last_mut() {
+          key.clear();
+        }
+        (self.key_started, self.in_key) = (false, true);
+      }
+      TokenInfo::ObjectValueStart => self.in_key = false,
+      TokenInfo::ArrayStart => {
+        self.segments.push(PathSegment::Index(0));
+        self.in_key = false;
+      }
+      TokenInfo::ArrayNext => {
+        if let Some(PathSegment::Index(index)) = self.segments.last_mut() {
+          *index += 1;
+        }
+      }
+      TokenInfo::ObjectEnd | TokenInfo::ArrayEnd => {
+        self.segments.pop();
+        /* the parent (if any) is in a value */
+        (self.key_started, self.in_key) = (true, false);
+      }
+      _ if !self.in_key => {}
+      TokenInfo::StringStart | TokenInfo::IdentifierEscapeStart(..) => self.key_started = true,
+      TokenInfo::IdentifierNormal => {
+        self.key_started = true;
+        self.push_key(token.c);
+      }
+      TokenInfo::StringNormal => self.push_key(token.c),
+      TokenInfo::StringEscape(c)
+      | TokenInfo::StringEscapeUnicode(_, Some(c))
+      | TokenInfo::StringEscapeHex(_, Some(c))
+      | TokenInfo::IdentifierEscape(_, Some(c)) => self.push_key(c),
+      /* a lone surrogate is replaced with U+FFFD */
+      TokenInfo::StringEscapeSurrogate(_) => self.push_key(char::REPLACEMENT_CHARACTER),
+      _ => {}
+    }
+  }
+  fn to_pointer(&self) -> JsonPointer {
+    let mut segments = self.segments.clone();
+    if matches!(segments.last(), Some(PathSegment::Key(_))) && !self.key_started {
+      segments.pop();
+    }
+    JsonPointer { segments }
+  }
+}
+
+impl StreamParser {
+  /**
+  Enable tracking the path of the current value, see `current_path`.
+
+  # Panics
+  Panics if the parser is inside an array or an object.
+  */
+  pub fn enable_path_tracking(&mut self) {
+    assert!(self.raw.len == 0, "path tracking must be enabled outside of containers");
+    self.path.get_or_insert_with(Default::default);
+  }
+  /**
+  Get the JSON Pointer of the current value, or `None` if path tracking is not enabled.
+
+  The pointer includes the key being parsed, and the index of the element which is expected next.
+
+  # Example
+  ```rust
+  use efjson::{stream_parser::StreamParser, ParserOption};
+  let mut parser = StreamParser::new(ParserOption::empty());
+  parser.enable_path_tracking();
+  assert!(parser.feed("{\"servers\": [{}, {}, {}, {\"port\": \"80\", \"a/b\": x").is_err());
+  assert_eq!(parser.current_path().unwrap().to_string(), "/servers/3/a~1b");
+  assert_eq!(parser.get_depth(), 3);
+  ```
+  */
+  pub fn current_path(&self) -> Option<JsonPointer> {
+    self.path.as_ref().map(|path| path.to_pointer())
+  }
+  /** Get the number of arrays and objects containing the current position */
+  pub fn get_depth(&self) -> usize {
+    self.raw.len as usize
+  }
+}
@@ -0,0 +1,226 @@
+use super::outer::FLAG_MEET_CR;
+use super::{StreamError, StreamParser, Token};
+
+/** Whether the character breaks a line (`"\r\n"` is a single line break) */
+pub(crate) fn is_next_line(c: char) -> bool {
+  matches!(c, '\n' | '\r' | '\u{2028}' | '\u{2029}')
+}
+
+/**
+A position in the input, counted in several units.
+
+All fields start from 0.
+*/
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Hash)]
+pub struct TextPosition {
+  /** the offset in Unicode scalar values */
+  pub position: usize,
+  /** the offset in bytes (UTF-8) */
+  pub byte_position: usize,
+  pub line: usize,
+  /**
+  the column in Unicode scalar values
+  (a tab is one column, except for the positions of `LineIndex::with_tab_width`)
+  */
+  pub column: usize,
+  /** the column in UTF-16 code units, as used by LSP */
+  pub utf16_column: usize,
+}
+
+impl StreamError {
+  pub fn get_text_position(&self) -> TextPosition {
+    TextPosition {
+      position: self.position,
+      byte_position: self.byte_position,
+      line: self.line,
+      column: self.column,
+      utf16_column: self.utf16_column,
+    }
+  }
+}
+
+/** A token with the position of its character */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LocatedToken {
+  pub token: Token,
+  pub position: TextPosition,
+}
+
+impl StreamParser {
+  /** Get the current position in all units */
+  pub fn get_text_position(&self) -> TextPosition {
+    TextPosition {
+      position: self.get_position(),
+      byte_position: self.get_byte_position(),
+      line: self.get_line(),
+      column: self.get_column(),
+      utf16_column: self.get_utf16_column(),
+    }
+  }
+  /**
+  Get the position of `c` if it is the next character.
+
+  It differs from `get_text_position` only after a `'\r'`,
+  since the line is not moved until the next character is known.
+  */
+  pub(super) fn get_text_position_of(&self, c: char) -> TextPosition {
+    let mut position = self.get_text_position();
+    if self.raw.flag & FLAG_MEET_CR != 0 && c != '\n' && c != '\0' {
+      position.line += 1;
+      position.column = 0;
+      position.utf16_column = 0;
+    }
+    position
+  }
+  /** The same as `feed_one`, but the token is attached with the position of `c` */
+  pub fn feed_one_located(&mut self, c: char) -> Result<LocatedToken, StreamError> {
+    let position = self.get_text_position_of(c);
+    self.feed_one(c).map(|token| LocatedToken { token, position })
+  }
+  /**
+  The same as `feed`, but each token is attached with the position of its character
+  (a virtual comma, see `feed_one_into`, shares the position of the character after it).
+
+  # Example
+  ```rust
+  use efjson::{stream_parser::StreamParser, ParserOption};
+  let mut parser = StreamParser::new(ParserOption::all());
+  let tokens = parser.feed_located("[\"😀\",\n1]").unwrap();
+  let one = tokens[6].position;
+  assert_eq!((one.line, one.column, one.utf16_column), (1, 0, 0));
+  let comma = tokens[4].position;
+  assert_eq!((comma.position, comma.byte_position, comma.column, comma.utf16_column), (4, 7, 4, 5));
+  ```
+  */
+  pub fn feed_located(&mut self, s: &str) -> Result<Vec<LocatedToken>, StreamError> {
+    let mut tokens = Vec::with_capacity(s.len());
+    for c in s.chars() {
+      let position = self.get_text_position_of(c);
+      let (comma, token) = self.feed_pair(c)?;
+      tokens.extend(comma.into_iter().chain([token]).map(|token| LocatedToken { token, position }));
+    }
+    Ok(tokens)
+  }
+}
+
+/**
+An index of the lines of a source text, converting positions between bytes, characters and UTF-16 code units.
+
+Lines are broken in the same way as `StreamParser`
+(by `"\n"`, `"\r"`, `"\r\n"`, `U+2028` and `U+2029`).
+
+# Example
+```rust
+use efjson::stream_parser::LineIndex;
+let index = LineIndex::with_tab_width("{\n\t\"😀\": 1\n}", 4);
+let position = index.position_of_byte(10);
+assert_eq!((position.line, position.column, position.utf16_column), (1, 8, 6));
+assert_eq!(index.position_of_utf16(1, 6), position);
+assert_eq!(index.position_of_char(position.position), position);
+```
+*/
+#[derive(Debug, Clone)]
+pub struct LineIndex<'a> {
+  source: &'a str,
+  /** the offsets in bytes and in characters of the start of each line */
+  lines: Vec<(usize, usize)>,
+  tab_width: usize,
+}
+
+impl<'a> LineIndex<'a> {
+  /** Create an index whose columns count a tab as one character (the same as `StreamParser`) */
+  pub fn new(source: &'a str) -> Self {
+    Self::with_tab_width(source, 1)
+  }
+  /**
+  Create an index whose columns move to the next multiple of `tab_width` at a tab.
+
+  The tab width only applies to the positions returned by the index,
+  the columns reported by `StreamParser` (and `LocatedToken`) always count a tab as one column,
+  use `position_of_char` to convert them.
+
+  # Example
+  ```rust
+  use efjson::{stream_parser::{LineIndex, StreamParser}, ParserOption};
+  let source = "[\t1\tx]";
+  let err = StreamParser::parse(ParserOption::empty(), source).unwrap_err();
+  assert_eq!(err.column, 4);
+  let index = LineIndex::with_tab_width(source, 4);
+  assert_eq!(index.position_of_char(err.position).column, 8);
+  ```
+  */
+  pub fn with_tab_width(source: &'a str, tab_width: usize) -> Self {
+    let mut lines = vec![(0, 0)];
+    for (chars, (i, c)) in source.char_indices().enumerate() {
+      if is_next_line(c) && !(c == '\r' && source[i + 1..].starts_with('\n')) {
+        lines.push((i + c.len_utf8(), chars + 1));
+      }
+    }
+    Self { source, lines, tab_width: tab_width.max(1) }
+  }
+
+  pub fn get_tab_width(&self) -> usize {
+    self.tab_width
+  }
+  pub fn get_line_count(&self) -> usize {
+    self.lines.len()
+  }
+  /** Get the text of a line without the line break */
+  pub fn get_line_text(&self, line: usize) -> &'a str {
+    let text = &self.source[self.lines[line].0..self.line_end(line)];
+    text.find(is_next_line).map_or(text, |n| &text[..n])
+  }
+
+  /** Get the position of a byte offset (rounded down to a character boundary) */
+  pub fn position_of_byte(&self, byte_position: usize) -> TextPosition {
+    let line = self.lines.partition_point(|&(byte, _)| byte <= byte_position) - 1;
+    self.scan(line, self.line_end(line), |position, c| {
+      position.byte_position + c.len_utf8() > byte_position
+    })
+  }
+  /** Get the position of a character offset */
+  pub fn position_of_char(&self, position: usize) -> TextPosition {
+    let line = self.lines.partition_point(|&(_, chars)| chars <= position) - 1;
+    self.scan(line, self.line_end(line), |pos, _| pos.position >= position)
+  }
+  /** Get the position of a UTF-16 column (rounded down to a character, clamped to the line) */
+  pub fn position_of_utf16(&self, line: usize, utf16_column: usize) -> TextPosition {
+    let line = line.min(self.lines.len() - 1);
+    let end = self.lines[line].0 + self.get_line_text(line).len();
+    self.scan(line, end, |position, c| position.utf16_column + c.len_utf16() > utf16_column)
+  }
+  /** Get the position of a column (rounded down to a character, clamped to the line) */
+  pub fn position_of_column(&self, line: usize, column: usize) -> TextPosition {
+    let line = line.min(self.lines.len() - 1);
+    let end = self.lines[line].0 + self.get_line_text(line).len();
+    self.scan(line, end, |position, _| position.column >= column)
+  }
+
+  fn line_end(&self, line: usize) -> usize {
+    self.lines.get(line + 1).map_or(self.source.len(), |&(byte, _)| byte)
+  }
+  /** Move from the start of `line` until `stop` returns true or `end` is reached */
+  fn scan(
+    &self,
+    line: usize,
+    end: usize,
+    mut stop: impl FnMut(&TextPosition, char) -> bool,
+  ) -> TextPosition {
+    let (byte_position, position) = self.lines[line];
+    let mut result = TextPosition { position, byte_position, line, column: 0, utf16_column: 0 };
+    for c in self.source[byte_position..end].chars() {
+      if stop(&result, c) {
+        break;
+      }
+      result.position += 1;
+      result.byte_position += c.len_utf8();
+      result.utf16_column += c.len_utf16();
+      result.column = if c == '\t' {
+        (result.column / self.tab_width + 1) * self.tab_width
+      } else {
+        result.column + 1
+      };
+    }
+    result
+  }
+}
@@ -0,0 +1,126 @@
+use std::io::Read;
+
+use super::{Limits, StreamError, StreamParser, Token};
+use crate::ParserOption;
+
+/** The size of the buffer used when reading from a `Read` */
+const READER_BUFFER_SIZE: usize = 8192;
+
+/** The error of parsing from a reader */
+#[derive(Debug)]
+pub enum ReaderError {
+  IoError(std::io::Error),
+  StreamParserError(StreamError),
+}
+impl std::fmt::Display for ReaderError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      ReaderError::IoError(err) => write!(f, "{}", err),
+      ReaderError::StreamParserError(err) => write!(f, "{}", err),
+    }
+  }
+}
+impl std::error::Error for ReaderError {
+  fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+    match self {
+      ReaderError::IoError(err) => Some(err),
+      ReaderError::StreamParserError(_) => None,
+    }
+  }
+}
+
+struct ReaderIter<R: Read> {
+  parser: StreamParser,
+  reader: R,
+  buf: Box<[u8]>,
+  pos: usize,
+  len: usize,
+  eof: bool,
+  done: bool,
+  /** the token after a comma inserted, see `StreamParser::feed_one_into` */
+  pending: Option<Token>,
+}
+impl<R: Read> Iterator for ReaderIter<R> {
+  type Item = Result<Token, ReaderError>;
+  fn next(&mut self) -> Option<Self::Item> {
+    if let Some(token) = self.pending.take() {
+      return Some(Ok(token));
+    }
+    while !self.done {
+      if self.pos < self.len {
+        let b = self.buf[self.pos];
+        self.pos += 1;
+        match self
+          .parser
+          .decode_byte(b)
+          .and_then(|c| c.map(|c| self.parser.feed_pair(c)).transpose())
+        {
+          Ok(Some((Some(comma), token))) => {
+            self.pending = Some(token);
+            return Some(Ok(comma));
+          }
+          Ok(Some((None, token))) => return Some(Ok(token)),
+          Ok(None) => {}
+          Err(err) => {
+            self.done = true;
+            return Some(Err(ReaderError::StreamParserError(err)));
+          }
+        }
+      } else if self.eof {
+        self.done = true;
+        return Some(self.parser.feed_one('\0').map_err(ReaderError::StreamParserError));
+      } else {
+        match self.reader.read(&mut self.buf) {
+          Ok(0) => self.eof = true,
+          Ok(n) => (self.pos, self.len) = (0, n),
+          Err(err) if err.kind() == std::io::ErrorKind::Interrupted => {}
+          Err(err) => {
+            self.done = true;
+            return Some(Err(ReaderError::IoError(err)));
+          }
+        }
+      }
+    }
+    None
+  }
+}
+
+impl StreamParser {
+  /**
+  Parse UTF-8 input from a reader, and return an iterator of tokens (including `Eof`).
+
+  The input is read through a fixed-size buffer, so the whole input is never held in memory.
+  The iterator stops after the first error.
+
+  # Example
+  ```rust
+  use efjson::{stream_parser::StreamParser, ParserOption};
+  let reader = std::io::Cursor::new(b"[1,2]");
+  let tokens = StreamParser::from_reader(ParserOption::default(), reader).collect::<Result<Vec<_>, _>>();
+  assert_eq!(tokens.unwrap().len(), 6);
+  ```
+  */
+  pub fn from_reader(
+    option: ParserOption,
+    reader: impl Read,
+  ) -> impl Iterator<Item = Result<Token, ReaderError>> {
+    Self::from_reader_with_limits(option, Limits::default(), reader)
+  }
+  /** The same as `from_reader`, but with limits, see `StreamParser::with_limits` */
+  pub fn from_reader_with_limits(
+    option: ParserOption,
+    limits: Limits,
+    reader: impl Read,
+  ) -> impl Iterator<Item = Result<Token, ReaderError>> {
+    ReaderIter {
+      parser: StreamParser::with_limits(option, limits),
+      reader,
+      buf: vec![0; READER_BUFFER_SIZE].into_boxed_slice(),
+      pos: 0,
+      len: 0,
+      eof: false,
+      done: false,
+      pending: None,
+    }
+  }
+}
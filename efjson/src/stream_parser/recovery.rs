@@ -0,0 +1,275 @@
+use super::outer::*;
+use super::{is_next_line, Location, Stage, StreamError, StreamParser, Token};
+use crate::ParserOption;
+
+/** The characters where the parser resynchronizes after skipping */
+fn is_sync_point(c: char) -> bool {
+  matches!(c, ',' | ']' | '}' | '\0') || is_next_line(c)
+}
+
+impl StreamParser {
+  /** Whether the container at `depth` (starting from 1) is an array */
+  fn is_array_at(&self, depth: usize) -> bool {
+    if depth == self.raw.len as usize {
+      matches!(self.raw.location, LOC_ELEMENT_FIRST_START | LOC_ELEMENT_START | LOC_ELEMENT_END)
+    } else {
+      /* each bit records the location where the next container starts */
+      (self.raw.get_stack()[depth >> 3] >> (depth & 7)) & 1 != 0
+    }
+  }
+  /** Move over a character without feeding it (see `efjsonStreamParser__movePosition`) */
+  pub(super) fn skip_char(&mut self, c: char) {
+    let raw = &mut self.raw;
+    if raw.flag & FLAG_MEET_CR != 0 {
+      if c != '\n' {
+        raw.line += 1;
+        raw.column = 0;
+      }
+      raw.flag &= !FLAG_MEET_CR;
+    }
+    raw.position += 1;
+    if c == '\r' {
+      raw.column += 1;
+      raw.flag |= FLAG_MEET_CR;
+    } else if is_next_line(c) {
+      raw.line += 1;
+      raw.column = 0;
+    } else {
+      raw.column += 1;
+    }
+    self.byte_position += c.len_utf8();
+    if c > '\u{FFFF}' {
+      self.track_astral(&[c]);
+    }
+  }
+  /** Feed a character which is not in the input, the position remains unchanged */
+  pub(super) fn feed_virtual(&mut self, c: char) -> Result<Token, StreamError> {
+    let (position, line, column, flag) =
+      (self.raw.position, self.raw.line, self.raw.column, self.raw.flag);
+    let result = self.feed_sized(c, 0);
+    self.raw.position = position;
+    self.raw.line = line;
+    self.raw.column = column;
+    self.raw.flag = (self.raw.flag & !FLAG_MEET_CR) | (flag & FLAG_MEET_CR);
+    result
+  }
+  /** Give up the value (or comment) being parsed, as if it has ended */
+  fn abandon_value(&mut self) {
+    let raw = &mut self.raw;
+    if !(VAL_COMMENT_MAY_START..=VAL_MULTI_LINE_COMMENT_MAY_END).contains(&raw.state) {
+      raw.location = match raw.location {
+        LOC_ROOT_START => LOC_ROOT_END,
+        LOC_KEY_FIRST_START | LOC_KEY_START => LOC_KEY_END,
+        LOC_VALUE_START => LOC_VALUE_END,
+        LOC_ELEMENT_FIRST_START | LOC_ELEMENT_START => LOC_ELEMENT_END,
+        location => location,
+      };
+    }
+    raw.state = VAL_EMPTY;
+    raw.flag &= FLAG_MEET_CR;
+    raw.substate = 0;
+    raw.escape = 0;
+    raw.set_prev_pair(0);
+  }
+  /** Move to the end of the innermost container, so that its closing bracket is accepted */
+  fn force_container_end(&mut self, is_array: bool) {
+    self.raw.state = VAL_EMPTY;
+    self.raw.location = if is_array { LOC_ELEMENT_END } else { LOC_VALUE_END };
+  }
+  /** Close the innermost container with a virtual bracket */
+  fn close_virtually(&mut self, dest: &mut Vec<Token>) {
+    let is_array = self.is_array_at(self.raw.len as usize);
+    self.force_container_end(is_array);
+    if let Ok(token) = self.feed_virtual(if is_array { ']' } else { '}' }) {
+      dest.push(token);
+    }
+  }
+}
+
+/**
+A layer over `StreamParser` which recovers from errors and collects them, for editors and linters.
+
+When an error occurs, the parser:
+- gives up the value being parsed (no end token is emitted for it);
+- inserts a virtual `,` or `:` if it is missing;
+- inserts virtual closing brackets if a closing bracket does not match,
+  or drops the bracket if no container matches;
+- otherwise skips the input until the next `,`, `]`, `}` or line break.
+
+At the end of input, unclosed containers are closed with virtual brackets.
+
+# Note
+Virtual tokens are not in the input, they share the position of the next character.
+Only one error is collected for each skipped run of input.
+
+# Example
+```rust
+use efjson::{stream_parser::{ErrorKind, RecoveringStreamParser}, ParserOption};
+let (tokens, errors) = RecoveringStreamParser::parse(ParserOption::empty(), "[1 2, tru, {\"a\": 3]");
+assert_eq!(errors.iter().map(|e| e.position).collect::<Vec<_>>(), [3, 9, 18]);
+assert_eq!(errors[2].kind, ErrorKind::WrongBracket);
+/* a virtual comma before `2`, a virtual `}` before `]` */
+assert_eq!(tokens.iter().map(|token| token.c).collect::<String>(), "[1 ,2, tru, {\"a\": 3}]\0");
+```
+*/
+#[derive(Debug, Clone)]
+pub struct RecoveringStreamParser {
+  parser: StreamParser,
+  errors: Vec<StreamError>,
+  skipping: bool,
+}
+impl From<StreamParser> for RecoveringStreamParser {
+  fn from(parser: StreamParser) -> Self {
+    Self { parser, errors: Vec::new(), skipping: false }
+  }
+}
+
+impl RecoveringStreamParser {
+  pub fn new(option: ParserOption) -> Self {
+    Self::from(StreamParser::new(option))
+  }
+
+  /** Get the errors collected so far */
+  pub fn get_errors(&self) -> &[StreamError] {
+    &self.errors
+  }
+  pub fn get_line(&self) -> usize {
+    self.parser.get_line()
+  }
+  pub fn get_column(&self) -> usize {
+    self.parser.get_column()
+  }
+  pub fn get_position(&self) -> usize {
+    self.parser.get_position()
+  }
+  pub fn get_byte_position(&self) -> usize {
+    self.parser.get_byte_position()
+  }
+  pub fn get_location(&self) -> Location {
+    self.parser.get_location()
+  }
+  pub fn get_stage(&self) -> Stage {
+    self.parser.get_stage()
+  }
+
+  /**
+  Feed a single character to the parser and return the tokens (including virtual ones).
+
+  # Note
+  If the string is ended, call `end` instead of passing `'\0'`.
+  */
+  pub fn feed_one(&mut self, c: char) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    self.feed_one_into(c, &mut tokens);
+    tokens
+  }
+  pub fn feed(&mut self, s: &str) -> Vec<Token> {
+    let mut tokens = Vec::with_capacity(s.len());
+    self.feed_into(s, &mut tokens);
+    tokens
+  }
+  /** Feed a string to the parser and append the tokens to `dest` */
+  pub fn feed_into(&mut self, s: &str, dest: &mut Vec<Token>) {
+    for c in s.chars() {
+      self.feed_one_into(c, dest);
+    }
+  }
+  /** Notify the parser that the input is ended, return the remaining tokens and all errors */
+  pub fn end(mut self) -> (Vec<Token>, Vec<StreamError>) {
+    let mut tokens = Vec::new();
+    self.feed_one_into('\0', &mut tokens);
+    (tokens, self.errors)
+  }
+
+  fn feed_one_into(&mut self, c: char, dest: &mut Vec<Token>) {
+    if self.skipping {
+      if !is_sync_point(c) {
+        self.parser.skip_char(c);
+        return;
+      }
+      self.skipping = false;
+    }
+    match self.parser.feed_one_into(c, dest) {
+      Ok(()) => {}
+      Err(err) => {
+        self.errors.push(err);
+        if c == '\0' {
+          self.recover_eof(dest);
+        } else {
+          self.recover(c, dest);
+        }
+      }
+    }
+  }
+  fn recover(&mut self, c: char, dest: &mut Vec<Token>) {
+    let parser = &mut self.parser;
+    if parser.raw.state != VAL_EMPTY {
+      parser.abandon_value();
+      if parser.feed_one_into(c, dest).is_ok() {
+        return;
+      }
+    }
+
+    match c {
+      ']' | '}' => {
+        let is_array = c == ']';
+        let len = parser.raw.len as usize;
+        if let Some(depth) = (1..=len).rev().find(|&depth| parser.is_array_at(depth) == is_array) {
+          for _ in depth..len {
+            parser.close_virtually(dest);
+          }
+          parser.force_container_end(is_array);
+          if parser.feed_one_into(c, dest).is_ok() {
+            return;
+          }
+        }
+        parser.skip_char(c);
+      }
+      _ if is_sync_point(c) => parser.skip_char(c),
+      _ => {
+        let separator = match parser.raw.location {
+          LOC_KEY_END => Some(':'),
+          LOC_VALUE_END | LOC_ELEMENT_END => Some(','),
+          _ => None,
+        };
+        if let Some(separator) = separator {
+          let snapshot = parser.raw.snapshot();
+          if let Ok(virtual_token) = parser.feed_virtual(separator) {
+            if let Ok((comma, token)) = parser.feed_pair(c) {
+              dest.push(virtual_token);
+              dest.extend(comma);
+              dest.push(token);
+              return;
+            }
+            parser.raw.restore(&snapshot);
+          }
+        }
+        parser.skip_char(c);
+        self.skipping = true;
+      }
+    }
+  }
+  fn recover_eof(&mut self, dest: &mut Vec<Token>) {
+    let parser = &mut self.parser;
+    if parser.raw.state != VAL_EMPTY {
+      parser.abandon_value();
+    }
+    while parser.raw.len != 0 {
+      parser.close_virtually(dest);
+    }
+    if let Ok(token) = parser.feed_one('\0') {
+      dest.push(token);
+    }
+  }
+}
+
+impl RecoveringStreamParser {
+  /** Parse the whole string, return the tokens and all errors */
+  pub fn parse(option: ParserOption, s: &str) -> (Vec<Token>, Vec<StreamError>) {
+    let mut parser = RecoveringStreamParser::new(option);
+    let mut tokens = parser.feed(s);
+    let (rest, errors) = parser.end();
+    tokens.extend(rest);
+    (tokens, errors)
+  }
+}
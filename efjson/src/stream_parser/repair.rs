@@ -0,0 +1,246 @@
+use super::outer::*;
+use super::{ErrorKind, StreamError, StreamParser, TextPosition, Token, TokenInfo};
+use crate::ParserOption;
+
+/** A mistake repaired by the options of `ParserOption::make_repair` */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RepairKind {
+  /** a comma is inserted (`ParserOption::MISSING_COMMA`) */
+  MissingComma,
+  /** `True`, `False` or `None` (`ParserOption::PYTHON_LITERAL`) */
+  PythonLiteral,
+  /** a line break in a string (`ParserOption::UNESCAPED_NEWLINE`) */
+  UnescapedNewline,
+  /** the text after the root value is ignored (`ParserOption::TRAILING_TEXT`) */
+  TrailingText,
+}
+impl RepairKind {
+  pub fn as_str(self) -> &'static str {
+    match self {
+      RepairKind::MissingComma => "missing comma",
+      RepairKind::PythonLiteral => "Python literal",
+      RepairKind::UnescapedNewline => "unescaped line break in string",
+      RepairKind::TrailingText => "text after the root value",
+    }
+  }
+}
+impl std::fmt::Display for RepairKind {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.write_str(self.as_str())
+  }
+}
+
+/** A warning of a repaired mistake, see `StreamParser::get_warnings` */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RepairWarning {
+  pub kind: RepairKind,
+  /** the position of the character repaired (before which a comma is inserted) */
+  pub position: TextPosition,
+}
+
+/** The options reporting `RepairWarning` */
+const REPAIR_OPTIONS: u32 = ParserOption::MISSING_COMMA.bits()
+  | ParserOption::PYTHON_LITERAL.bits()
+  | ParserOption::UNESCAPED_NEWLINE.bits()
+  | ParserOption::TRAILING_TEXT.bits();
+
+impl StreamParser {
+  /** Whether any option of `ParserOption::make_repair` is enabled */
+  pub(super) fn is_repairing(&self) -> bool {
+    self.raw.option & REPAIR_OPTIONS != 0
+  }
+  /** Record the warning of the token of `c`, where `flag` is the flag before `c` is fed */
+  pub(super) fn track_repair(&mut self, token: &Token, flag: u8, position: TextPosition) {
+    let kind = match token.info {
+      TokenInfo::True(0, _) | TokenInfo::False(0, _) | TokenInfo::Null(0, _)
+        if token.c.is_ascii_uppercase() =>
+      {
+        RepairKind::PythonLiteral
+      }
+      /* `"\r\n"` is a single line break */
+      TokenInfo::StringNormal
+        if token.c == '\r' || (token.c == '\n' && flag & FLAG_MEET_CR == 0) =>
+      {
+        RepairKind::UnescapedNewline
+      }
+      TokenInfo::Whitespace
+        if flag & FLAG_TRAILING_TEXT == 0 && self.raw.flag & FLAG_TRAILING_TEXT != 0 =>
+      {
+        RepairKind::TrailingText
+      }
+      _ => return,
+    };
+    self.warnings.push(RepairWarning { kind, position });
+  }
+
+  /**
+  Get the warnings of the mistakes repaired so far, in the order of the input.
+
+  The warnings are not saved by `save_state`, and those after a checkpoint are dropped by `rollback`.
+
+  # Example
+  ```rust
+  use efjson::{stream_parser::{RepairKind, StreamParser}, ParserOption};
+  let mut parser = StreamParser::new(ParserOption::make_repair());
+  let tokens = parser.feed("{\"a\": True \"b\": [1 2 None]} done\0").unwrap();
+  let kinds: Vec<_> = parser.get_warnings().iter().map(|w| (w.kind, w.position.position)).collect();
+  assert_eq!(kinds, [
+    (RepairKind::PythonLiteral, 6),
+    (RepairKind::MissingComma, 11),
+    (RepairKind::MissingComma, 19),
+    (RepairKind::MissingComma, 21),
+    (RepairKind::PythonLiteral, 21),
+    (RepairKind::TrailingText, 28),
+  ]);
+  /* the inserted commas are in the tokens */
+  let text: String = tokens.iter().map(|token| token.c).collect();
+  assert_eq!(text, "{\"a\": True ,\"b\": [1 ,2 ,None]} done\0");
+  ```
+  */
+  pub fn get_warnings(&self) -> &[RepairWarning] {
+    &self.warnings
+  }
+  /** Take the warnings collected so far, see `get_warnings` */
+  pub fn take_warnings(&mut self) -> Vec<RepairWarning> {
+    std::mem::take(&mut self.warnings)
+  }
+
+  /**
+  Feed a single character to the parser and append its tokens to `dest`.
+
+  It differs from `feed_one` only when a character produces two tokens:
+  - with `ParserOption::MISSING_COMMA`, a virtual comma may be inserted before the character,
+    the comma is not in the input, it shares the position of the character;
+  - with `ParserOption::LONE_SURROGATE`, an escaped high surrogate is reported as lone
+    (`TokenInfo::StringEscapeSurrogate`) before the character which breaks the pair,
+    the token shares the character.
+
+  # Errors
+  The same as `feed_one` (except that no character is rejected with `ErrorKind::MultipleTokens`),
+  and nothing is appended.
+
+  # Example
+  The layers over `StreamParser` insert commas in the same way.
+  ```rust
+  use efjson::{stream_parser::*, ParserOption};
+  let option = ParserOption::MISSING_COMMA;
+  let text = |tokens: &[Token]| tokens.iter().map(|token| token.c).collect::<String>();
+  let mut tokens = Vec::new();
+  let mut parser = StreamParser::new(option);
+  for c in "[1 2]".chars() {
+    parser.feed_one_into(c, &mut tokens).unwrap();
+  }
+  assert_eq!(text(&tokens), "[1 ,2]");
+
+  let mut parser = StreamParser::new(option);
+  let located = parser.feed_located("[1 2]").unwrap();
+  assert_eq!(located[3].token.c, ',');
+  assert_eq!(located[3].position, located[4].position);
+  assert_eq!(text(&DecodingStreamParser::parse(option, b"[1 2]").unwrap()), "[1 ,2]\0");
+  let (tokens, errors) = RecoveringStreamParser::parse(option, "[1 2]");
+  assert_eq!((text(&tokens).as_str(), errors.len()), ("[1 ,2]\0", 0));
+  let texts = JsonSeqParser::parse(option, "\u{1E}[1 2]\n");
+  assert_eq!(text(texts[0].as_ref().unwrap()), "[1 ,2]\n");
+  let results = MultiDocumentParser::parse(option, "[1 2] [3]");
+  let tokens: Vec<_> = results.into_iter().map(|result| result.unwrap().token).collect();
+  assert_eq!(text(&tokens), "[1 ,2] [3]\0");
+  ```
+  */
+  pub fn feed_one_into(&mut self, c: char, dest: &mut Vec<Token>) -> Result<(), StreamError> {
+    let (comma, token) = self.feed_pair(c)?;
+    dest.extend(comma);
+    dest.push(token);
+    Ok(())
+  }
+  /**
+  Feed a single character, and return the token reported before it (a comma inserted or a lone surrogate, if any)
+  and its token
+  */
+  pub(crate) fn feed_pair(&mut self, c: char) -> Result<(Option<Token>, Token), StreamError> {
+    self.feed_pair_mode(c, true)
+  }
+  /**
+  `feed_pair`, but unless `pairs`, a character which produces two tokens is rejected
+  with `ErrorKind::MultipleTokens` and not fed
+  */
+  pub(super) fn feed_pair_mode(
+    &mut self,
+    c: char,
+    pairs: bool,
+  ) -> Result<(Option<Token>, Token), StreamError> {
+    if self.utf8.rest != 0 {
+      return Err(self.discard_utf8((self.utf8.total - self.utf8.rest) as usize + 1));
+    }
+    self.feed_pair_sized(c, c.len_utf8(), pairs)
+  }
+  /** `feed_pair_mode` for a character which takes `len` bytes in the input */
+  pub(super) fn feed_pair_sized(
+    &mut self,
+    c: char,
+    len: usize,
+    pairs: bool,
+  ) -> Result<(Option<Token>, Token), StreamError> {
+    #[cfg(not(feature = "no-combine-surrogate"))]
+    if self.raw.state == VAL_STRING_UNICODE_NEXT {
+      return self.feed_after_surrogate(c, len, pairs);
+    }
+    let err = match self.feed_sized(c, len) {
+      Ok(token) => return Ok((None, token)),
+      Err(err) => err,
+    };
+    let raw = &self.raw;
+    let after_value = matches!(raw.location, LOC_VALUE_END | LOC_ELEMENT_END)
+      || (VAL_NUMBER..=VAL_NUMBER_EXPONENT).contains(&raw.state)
+      || (VAL_NUMBER_HEX..=VAL_NUMBER_BIN).contains(&raw.state);
+    if raw.option & ParserOption::MISSING_COMMA.bits() == 0
+      || c == '\0'
+      || raw.len == 0
+      || !after_value
+      || err.kind.is_limit_exceeded()
+      || err.kind.is_ijson_violation()
+      || err.kind == ErrorKind::InvalidInputUtf
+    {
+      return Err(err);
+    }
+    let checkpoint = self.checkpoint();
+    let position = self.get_text_position_of(c);
+    if let Ok(comma) = self.feed_virtual(',') {
+      if matches!(comma.info, TokenInfo::ArrayNext | TokenInfo::ObjectNext) {
+        self.warnings.push(RepairWarning { kind: RepairKind::MissingComma, position });
+        if let Ok(token) = self.feed_sized(c, len) {
+          if pairs {
+            return Ok((Some(comma), token));
+          }
+          self.rollback(&checkpoint);
+          return Err(self.make_error(c, ErrorKind::MultipleTokens));
+        }
+      }
+    }
+    self.rollback(&checkpoint);
+    Err(err)
+  }
+  /** Feed a character after an escaped high surrogate, which is fed again if it breaks the pair */
+  #[cfg(not(feature = "no-combine-surrogate"))]
+  fn feed_after_surrogate(
+    &mut self,
+    c: char,
+    len: usize,
+    pairs: bool,
+  ) -> Result<(Option<Token>, Token), StreamError> {
+    if self.raw.option & ParserOption::LONE_SURROGATE.bits() == 0 {
+      return Ok((None, self.feed_sized(c, len)?));
+    }
+    let checkpoint = self.checkpoint();
+    let token = self.feed_sized(c, len)?;
+    if !matches!(token.info, TokenInfo::StringEscapeSurrogate(_)) {
+      return Ok((None, token));
+    }
+    let err = match self.feed_sized(c, len) {
+      Ok(next) if pairs => return Ok((Some(token), next)),
+      Ok(_) => None,
+      Err(err) => Some(err),
+    };
+    self.rollback(&checkpoint);
+    Err(err.unwrap_or_else(|| self.make_error(c, ErrorKind::MultipleTokens)))
+  }
+}
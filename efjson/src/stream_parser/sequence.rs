@@ -0,0 +1,183 @@
+use super::outer::*;
+use super::{Category, Limits, StreamError, StreamParser, Token};
+use crate::ParserOption;
+
+/** The record separator (U+001E) which starts each text of a JSON text sequence */
+pub const RECORD_SEPARATOR: char = '\u{1E}';
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SeqState {
+  /** parsing a text */
+  Text,
+  /** the text has been yielded, waiting for the next separator */
+  Done,
+  /** an error occurred, waiting for the next separator */
+  Skipping,
+}
+
+/**
+A layer over `StreamParser` for JSON text sequences (RFC 7464, `application/json-seq`),
+where each text starts with `RS` (U+001E) and ends with `LF`.
+
+The tokens of a text are yielded together, once the text is complete:
+at the `LF` after its value, at the next `RS`, or at the end of input.
+
+# Errors
+An error does not stop the parser: the rest of the text is skipped until the next `RS`.
+
+Truncated texts are skipped silently and counted (see `get_truncated_count`),
+including top-level numbers, `true`, `false` and `null`
+which are not followed by whitespace, as required by the RFC.
+
+# Note
+`Limits` (except `max_input_length`) are applied to each text.
+
+# Example
+```rust
+use efjson::{stream_parser::{Category, JsonSeqParser}, ParserOption};
+let mut parser = JsonSeqParser::new(ParserOption::empty());
+let texts = parser.feed("\u{1E}{\"a\":1}\n\u{1E}[1,\n\u{1E}12\u{1E}\"b\"\n");
+parser.end();
+let texts: Vec<_> = texts.into_iter().map(Result::unwrap).collect();
+assert_eq!(texts.len(), 2);
+assert_eq!(texts[1][0].info.get_category(), Category::String);
+/* `[1,` and `12` are truncated */
+assert_eq!(parser.get_truncated_count(), 2);
+```
+*/
+#[derive(Debug, Clone)]
+pub struct JsonSeqParser {
+  parser: StreamParser,
+  tokens: Vec<Token>,
+  state: SeqState,
+  truncated: usize,
+}
+impl From<StreamParser> for JsonSeqParser {
+  fn from(parser: StreamParser) -> Self {
+    Self { parser, tokens: Vec::new(), state: SeqState::Text, truncated: 0 }
+  }
+}
+
+impl JsonSeqParser {
+  pub fn new(option: ParserOption) -> Self {
+    Self::from(StreamParser::new(option))
+  }
+  /** Create a parser with limits, see `StreamParser::with_limits` */
+  pub fn with_limits(option: ParserOption, limits: Limits) -> Self {
+    Self::from(StreamParser::with_limits(option, limits))
+  }
+
+  /** Get the number of truncated texts skipped so far */
+  pub fn get_truncated_count(&self) -> usize {
+    self.truncated
+  }
+  pub fn get_line(&self) -> usize {
+    self.parser.get_line()
+  }
+  pub fn get_column(&self) -> usize {
+    self.parser.get_column()
+  }
+  pub fn get_position(&self) -> usize {
+    self.parser.get_position()
+  }
+  pub fn get_byte_position(&self) -> usize {
+    self.parser.get_byte_position()
+  }
+
+  /**
+  Feed a single character to the parser and return the tokens of the text if it is complete.
+
+  # Note
+  If the string is ended, you need to explicitly pass `'\0'` to notify the parser (or call `end`).
+
+  # Errors
+  The same as `StreamParser::feed_one`, but the parser can still be used.
+  */
+  pub fn feed_one(&mut self, c: char) -> Result<Option<Vec<Token>>, StreamError> {
+    if c == RECORD_SEPARATOR || c == '\0' {
+      return Ok(self.next_text(c));
+    }
+    if self.state == SeqState::Skipping {
+      self.parser.skip_char(c);
+      return Ok(None);
+    }
+    match self.parser.feed_one_into(c, &mut self.tokens) {
+      Ok(()) if self.state == SeqState::Done => {
+        self.tokens.clear();
+        Ok(None)
+      }
+      Ok(()) => {
+        if c == '\n' && self.parser.raw.location == LOC_ROOT_END {
+          self.state = SeqState::Done;
+          return Ok(Some(std::mem::take(&mut self.tokens)));
+        }
+        Ok(None)
+      }
+      Err(err) => {
+        self.parser.skip_char(c);
+        self.tokens.clear();
+        self.state = SeqState::Skipping;
+        Err(err)
+      }
+    }
+  }
+  /** Feed a string to the parser, an error does not stop the parser */
+  pub fn feed(&mut self, s: &str) -> Vec<Result<Vec<Token>, StreamError>> {
+    s.chars().filter_map(|c| self.feed_one(c).transpose()).collect()
+  }
+  /** Notify the parser that the input is ended, and return the tokens of the last text */
+  pub fn end(&mut self) -> Option<Vec<Token>> {
+    self.next_text('\0')
+  }
+
+  /** Start a new text at a record separator or `'\0'`, and return the tokens of the current one */
+  fn next_text(&mut self, c: char) -> Option<Vec<Token>> {
+    let text = self.finish_text();
+    if c != '\0' {
+      self.parser.skip_char(c);
+    }
+    self.parser.reset_document();
+    self.state = SeqState::Text;
+    text
+  }
+
+  /** End the current text, return its tokens unless it is empty or truncated */
+  fn finish_text(&mut self) -> Option<Vec<Token>> {
+    let tokens = std::mem::take(&mut self.tokens);
+    if self.state != SeqState::Text {
+      return None;
+    }
+    let raw = &self.parser.raw;
+    if raw.location == LOC_ROOT_START && raw.state == VAL_EMPTY {
+      return None;
+    }
+    /* a scalar may be cut anywhere, unless it is followed by whitespace */
+    let scalar_end = tokens.last().is_some_and(|token| {
+      matches!(token.info.get_category(), Category::Null | Category::Boolean | Category::Number)
+    });
+    if raw.location == LOC_ROOT_END && raw.state == VAL_EMPTY && !scalar_end {
+      Some(tokens)
+    } else {
+      self.truncated += 1;
+      None
+    }
+  }
+}
+
+impl JsonSeqParser {
+  /** Parse the whole string, an error does not stop the parser */
+  pub fn parse(option: ParserOption, s: &str) -> Vec<Result<Vec<Token>, StreamError>> {
+    let mut parser = JsonSeqParser::new(option);
+    let mut results = parser.feed(s);
+    results.extend(parser.end().map(Ok));
+    results
+  }
+  /** Create an iterator of texts, `'\0'` is fed at the end of `iter` */
+  pub fn create_iter(
+    option: ParserOption,
+    iter: impl Iterator<Item = char>,
+  ) -> impl Iterator<Item = Result<Vec<Token>, StreamError>> {
+    let mut parser = JsonSeqParser::new(option);
+    iter.chain(std::iter::once('\0')).filter_map(move |c| parser.feed_one(c).transpose())
+  }
+}
@@ -0,0 +1,84 @@
+use super::{StreamError, StreamParser, Token};
+use std::ops::ControlFlow;
+
+/**
+A receiver of tokens, see `StreamParser::feed_to`.
+
+`B` is the value to stop with, for example the result of a deserializer.
+It is implemented for every `Deserializer` (with the `deserialize` feature)
+and for `EventEmitter` (with the `event` feature).
+*/
+pub trait TokenSink<B> {
+  /** Receive a token, return `ControlFlow::Break` to stop parsing */
+  fn token(&mut self, token: Token) -> ControlFlow<B>;
+}
+
+impl StreamParser {
+  /**
+  Feed a string to the parser and pass each token to `sink`, without collecting the tokens.
+
+  It stops at the first token for which `sink` breaks, and returns the break value.
+  The character of that token is consumed by the parser, and the rest of `s` is not fed.
+  If `sink` breaks on a virtual comma (see `feed_one_into`),
+  the token of the character after the comma is not passed to `sink` and is returned with the break value.
+
+  # Note
+  If the string is ended, you need to explicitly pass `'\0'` to notify the parser.
+
+  # Errors
+  The same as `feed_iter`: the tokens before the error have been passed to `sink`.
+
+  # Example
+  ```rust
+  use efjson::{stream_parser::{StreamParser, Token, TokenSink}, ParserOption};
+  use std::ops::ControlFlow;
+  /* count the elements of an array, stopping at the first string */
+  struct Counter(usize);
+  impl TokenSink<usize> for Counter {
+    fn token(&mut self, token: Token) -> ControlFlow<usize> {
+      match token.c {
+        ',' => self.0 += 1,
+        '"' => return ControlFlow::Break(self.0),
+        _ => {}
+      }
+      ControlFlow::Continue(())
+    }
+  }
+  let mut parser = StreamParser::new(ParserOption::empty());
+  let flow = parser.feed_to("[1, 2, 3, \"x\", 4]", &mut Counter(0)).unwrap();
+  assert_eq!(flow, ControlFlow::Break((3, None)));
+  assert_eq!(parser.get_position(), 11);
+
+  /* stop at the second comma, which is inserted before `3` */
+  struct Commas(usize);
+  impl TokenSink<()> for Commas {
+    fn token(&mut self, token: Token) -> ControlFlow<()> {
+      self.0 += (token.c == ',') as usize;
+      if self.0 == 2 { ControlFlow::Break(()) } else { ControlFlow::Continue(()) }
+    }
+  }
+  let mut parser = StreamParser::new(ParserOption::MISSING_COMMA);
+  let flow = parser.feed_to("[1, 2 3, 4]", &mut Commas(0)).unwrap();
+  let ControlFlow::Break(((), Some(token))) = flow else { unreachable!() };
+  assert_eq!((token.c, parser.get_position()), ('3', 7));
+  ```
+  */
+  pub fn feed_to<B>(
+    &mut self,
+    s: &str,
+    sink: &mut impl TokenSink<B>,
+  ) -> Result<ControlFlow<(B, Option<Token>)>, StreamError> {
+    for c in s.chars() {
+      let (comma, token) = self.feed_pair(c)?;
+      if let Some(comma) = comma {
+        if let ControlFlow::Break(b) = sink.token(comma) {
+          return Ok(ControlFlow::Break((b, Some(token))));
+        }
+      }
+      if let ControlFlow::Break(b) = sink.token(token) {
+        return Ok(ControlFlow::Break((b, None)));
+      }
+    }
+    Ok(ControlFlow::Continue(()))
+  }
+}
@@ -0,0 +1,466 @@
+use super::outer::*;
+use super::{IJsonChecker, PathSegment, PathTracker, StreamParser};
+use crate::ParserOption;
+use std::sync::Arc;
+
+/** The first bytes of a saved state */
+const STATE_MAGIC: &[u8; 4] = b"EFJS";
+/** The version of the format of saved states, increased on incompatible changes */
+const STATE_VERSION: u8 = 1;
+
+/** The error of `StreamParser::restore_state` */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StateError {
+  /** the data is not a saved state, or it is truncated */
+  Malformed,
+  /** the state is saved by an incompatible version */
+  UnsupportedVersion(u8),
+  /** the data is well-formed, but it is not a reachable state of the parser */
+  Invalid,
+}
+impl std::fmt::Display for StateError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      StateError::Malformed => write!(f, "malformed parser state"),
+      StateError::UnsupportedVersion(version) => {
+        write!(f, "unsupported parser state version {}", version)
+      }
+      StateError::Invalid => write!(f, "invalid parser state"),
+    }
+  }
+}
+impl std::error::Error for StateError {}
+
+struct StateReader<'a> {
+  data: &'a [u8],
+}
+impl<'a> StateReader<'a> {
+  fn bytes(&mut self, len: usize) -> Result<&'a [u8], StateError> {
+    if self.data.len() < len {
+      return Err(StateError::Malformed);
+    }
+    let (bytes, rest) = self.data.split_at(len);
+    self.data = rest;
+    Ok(bytes)
+  }
+  fn array<const N: usize>(&mut self) -> Result<[u8; N], StateError> {
+    self.bytes(N)?.try_into().map_err(|_| StateError::Malformed)
+  }
+  fn u8(&mut self) -> Result<u8, StateError> {
+    Ok(self.array::<1>()?[0])
+  }
+  fn u16(&mut self) -> Result<u16, StateError> {
+    Ok(u16::from_le_bytes(self.array()?))
+  }
+  fn u32(&mut self) -> Result<u32, StateError> {
+    Ok(u32::from_le_bytes(self.array()?))
+  }
+  fn usize(&mut self) -> Result<usize, StateError> {
+    usize::try_from(u64::from_le_bytes(self.array()?)).map_err(|_| StateError::Invalid)
+  }
+  fn string(&mut self) -> Result<String, StateError> {
+    let len = self.usize()?;
+    let s = std::str::from_utf8(self.bytes(len)?).map_err(|_| StateError::Invalid)?;
+    Ok(s.to_string())
+  }
+}
+
+fn write_usize(out: &mut Vec<u8>, value: usize) {
+  out.extend_from_slice(&(value as u64).to_le_bytes());
+}
+fn write_str(out: &mut Vec<u8>, s: &str) {
+  write_usize(out, s.len());
+  out.extend_from_slice(s.as_bytes());
+}
+
+/** The number of hex digits accumulated in `escape`, `None` if `escape` is not in use */
+fn escape_digits(state: u8, substate: u8) -> Option<u8> {
+  match state {
+    VAL_STRING_UNICODE | VAL_STRING_ESCAPE_HEX => Some(substate),
+    VAL_IDENTIFIER_ESCAPE => substate.checked_sub(1),
+    /* `escape` is the first half of the pair until `\u` (see `is_valid_state`) */
+    #[cfg(not(feature = "no-combine-surrogate"))]
+    VAL_STRING_UNICODE_NEXT | VAL_IDENTIFIER_ESCAPE_NEXT => substate.checked_sub(2),
+    _ => None,
+  }
+}
+/** Check the nesting stack of `len` bits restored by `restore_state` */
+fn is_valid_stack(stack: &[u8], len: usize) -> bool {
+  /* the bit of the root container is always set, and the bits above `len` are cleared by `save_state` */
+  len == 0 || (stack[0] & 1 != 0 && (len.is_multiple_of(8) || stack[len / 8] >> (len % 8) == 0))
+}
+/** The maximum `substate` of each state (see `efjsonVal__` and `efjsonStreamParser`) */
+fn max_substate(state: u8) -> u8 {
+  match state {
+    VAL_NULL | VAL_TRUE => 3,
+    VAL_FALSE => 4,
+    VAL_NUMBER_INFINITY => 7,
+    VAL_NUMBER_NAN => 2,
+    VAL_STRING_UNICODE => 3,
+    #[cfg(not(feature = "no-combine-surrogate"))]
+    VAL_STRING_UNICODE_NEXT | VAL_IDENTIFIER_ESCAPE_NEXT => 5,
+    VAL_IDENTIFIER_ESCAPE => 5,
+    VAL_STRING_ESCAPE_HEX => 1,
+    VAL_NUMBER_FRACTION | VAL_NUMBER_EXPONENT => 2,
+    VAL_NUMBER_HEX..=VAL_NUMBER_BIN => 1,
+    _ => u8::MAX,
+  }
+}
+
+/*
+The states are saved as numbered with the feature `no-combine-surrogate` disabled,
+so that the saved states do not depend on the features.
+*/
+#[cfg(not(feature = "no-combine-surrogate"))]
+fn encode_state(state: u8) -> u8 {
+  state
+}
+#[cfg(not(feature = "no-combine-surrogate"))]
+fn decode_state(state: u8) -> Option<u8> {
+  Some(state)
+}
+#[cfg(feature = "no-combine-surrogate")]
+fn encode_state(state: u8) -> u8 {
+  match state {
+    VAL_NUMBER..VAL_IDENTIFIER_ESCAPE => state + 1,
+    VAL_IDENTIFIER_ESCAPE.. => state + 2,
+    _ => state,
+  }
+}
+#[cfg(feature = "no-combine-surrogate")]
+fn decode_state(state: u8) -> Option<u8> {
+  /* `STRING_UNICODE_NEXT` and `IDENTIFIER_ESCAPE_NEXT` */
+  match state {
+    7 | 24 => None,
+    8..24 => Some(state - 1),
+    25.. => Some(state - 2),
+    _ => Some(state),
+  }
+}
+
+fn read_path(reader: &mut StateReader, len: usize) -> Result<PathTracker, StateError> {
+  let [key_started, in_key] = reader.array()?;
+  if key_started > 1 || in_key > 1 || reader.usize()? != len {
+    return Err(StateError::Invalid);
+  }
+  let mut segments = Vec::with_capacity(len.min(reader.data.len()));
+  for _ in 0..len {
+    segments.push(match reader.u8()? {
+      0 => PathSegment::Index(reader.usize()?),
+      1 => PathSegment::Key(reader.string()?),
+      _ => return Err(StateError::Malformed),
+    });
+  }
+  Ok(PathTracker { segments, key_started: key_started != 0, in_key: in_key != 0 })
+}
+
+fn write_ijson(out: &mut Vec<u8>, checker: &IJsonChecker) {
+  out.push(checker.in_key as u8);
+  match checker.key.as_ref() {
+    Some((key, identifier)) => {
+      out.push(1 + *identifier as u8);
+      write_str(out, key);
+    }
+    None => out.push(0),
+  }
+  match checker.number.as_ref() {
+    Some(number) => {
+      out.push(1);
+      write_str(out, number);
+    }
+    None => out.push(0),
+  }
+  write_usize(out, checker.keys.len());
+  for keys in &checker.keys {
+    match keys {
+      Some(keys) => {
+        /* sorted to make the state deterministic */
+        let mut keys: Vec<_> = keys.iter().collect();
+        keys.sort_unstable();
+        out.push(1);
+        write_usize(out, keys.len());
+        keys.iter().for_each(|key| write_str(out, key));
+      }
+      None => out.push(0),
+    }
+  }
+}
+fn read_ijson(reader: &mut StateReader, len: usize) -> Result<IJsonChecker, StateError> {
+  let in_key = match reader.u8()? {
+    flag @ (0 | 1) => flag != 0,
+    _ => return Err(StateError::Invalid),
+  };
+  let key = match reader.u8()? {
+    0 => None,
+    tag @ (1 | 2) => Some((reader.string()?, tag == 2)),
+    _ => return Err(StateError::Malformed),
+  };
+  let number = match reader.u8()? {
+    0 => None,
+    1 => Some(reader.string()?),
+    _ => return Err(StateError::Malformed),
+  };
+  if reader.usize()? != len {
+    return Err(StateError::Invalid);
+  }
+  let mut keys = Vec::with_capacity(len.min(reader.data.len()));
+  for _ in 0..len {
+    keys.push(match reader.u8()? {
+      0 => None,
+      1 => {
+        let count = reader.usize()?;
+        Some(Arc::new((0..count).map(|_| reader.string()).collect::<Result<_, _>>()?))
+      }
+      _ => return Err(StateError::Malformed),
+    });
+  }
+  Ok(IJsonChecker { keys, key, in_key, number })
+}
+
+impl StreamParser {
+  /**
+  Save the state of the parser, which can be restored by `restore_state` (even in another process).
+
+  The state includes the position, the option, the state machine (with the nesting stack),
+  an incomplete UTF-8 sequence fed by `feed_byte`, the counters of `Limits`, the tracked path
+  and the keys seen by the checks of I-JSON.
+  The limits themselves are not saved.
+
+  # Example
+  ```rust
+  use efjson::{stream_parser::StreamParser, ParserOption};
+  let mut parser = StreamParser::new(ParserOption::all());
+  parser.feed("{\"a\": [1, {\"b\": \"x").unwrap();
+  let state = parser.save_state();
+
+  let mut resumed = StreamParser::new(ParserOption::empty());
+  resumed.restore_state(&state).unwrap();
+  assert_eq!(resumed.get_position(), 18);
+  resumed.feed("y\"}],\n// done\n}").unwrap();
+  assert!(resumed.feed_one('\0').is_ok());
+  ```
+  */
+  pub fn save_state(&self) -> Vec<u8> {
+    let raw = &self.raw;
+    let len = raw.len as usize;
+    let mut out = Vec::with_capacity(96 + len.div_ceil(8));
+    out.extend_from_slice(STATE_MAGIC);
+    out.push(STATE_VERSION);
+    out.extend_from_slice(&raw.option.to_le_bytes());
+    write_usize(&mut out, raw.position);
+    write_usize(&mut out, raw.line);
+    write_usize(&mut out, raw.column);
+    write_usize(&mut out, self.byte_position);
+    write_usize(&mut out, self.astral);
+    write_usize(&mut out, self.astral_line);
+    out.extend_from_slice(&[raw.location, encode_state(raw.state), raw.flag, raw.substate]);
+    out.extend_from_slice(&raw.escape.to_le_bytes());
+    out.extend_from_slice(&raw.get_prev_pair().to_le_bytes());
+    out.extend_from_slice(&self.utf8.code.to_le_bytes());
+    out.extend_from_slice(&self.utf8.rest.to_le_bytes());
+    out.extend_from_slice(&self.utf8.total.to_le_bytes());
+    write_usize(&mut out, len);
+    out.extend_from_slice(raw.get_stack());
+    /* the bits above `len` are left by the containers closed */
+    if let Some(last) = out.last_mut().filter(|_| !len.is_multiple_of(8)) {
+      *last &= (1 << (len % 8)) - 1;
+    }
+    match self.limits.as_ref() {
+      Some(limiter) => {
+        let (length, nodes, counts) = limiter.get_counters();
+        out.push(1);
+        write_usize(&mut out, length);
+        write_usize(&mut out, nodes);
+        write_usize(&mut out, counts.len());
+        counts.iter().for_each(|&count| write_usize(&mut out, count));
+      }
+      None => out.push(0),
+    }
+    match self.path.as_ref() {
+      Some(path) => {
+        out.extend_from_slice(&[1, path.key_started as u8, path.in_key as u8]);
+        write_usize(&mut out, path.segments.len());
+        for segment in &path.segments {
+          match segment {
+            PathSegment::Index(index) => {
+              out.push(0);
+              write_usize(&mut out, *index);
+            }
+            PathSegment::Key(key) => {
+              out.push(1);
+              write_str(&mut out, key);
+            }
+          }
+        }
+      }
+      None => out.push(0),
+    }
+    match self.ijson.as_ref() {
+      Some(checker) => {
+        out.push(1);
+        write_ijson(&mut out, checker);
+      }
+      None => out.push(0),
+    }
+    out
+  }
+
+  /**
+  Restore the state saved by `save_state`, replacing the current state (including the option).
+
+  The limits, path tracking and I-JSON checks of this parser are kept.
+  If the state is saved without limits, the counters of `Limits` start from 0;
+  if it is saved without path tracking, the path is unknown (empty);
+  if it is saved without I-JSON checks, the keys parsed before are unknown.
+
+  # Errors
+  Returns `StateError` if the state is not saved by `save_state` of a compatible version,
+  or it is nested too deeply for the stack of the feature `fixed-stack`,
+  and the parser remains unchanged.
+  The fields of the state machine are checked before they are handed to the C parser.
+
+  # Example
+  ```rust
+  use efjson::{stream_parser::{StateError, StreamParser}, ParserOption};
+  let mut parser = StreamParser::new(ParserOption::empty());
+  parser.feed("[\"\\u1").unwrap();
+  let state = parser.save_state();
+  /* the layout: magic, version, option, 6 counters, then location, state, flag and substate */
+  let (version, machine) = (4, 4 + 1 + 4 + 6 * 8);
+  let escape = machine + 4;
+  /* escape, pair, UTF-8 code, rest and total, then the length of the stack */
+  let stack = escape + 2 + 2 + 4 + 2 + 2 + 8;
+  let mut bad = state.clone();
+  bad[version] = 0;
+  assert_eq!(parser.restore_state(&bad), Err(StateError::UnsupportedVersion(0)));
+  assert_eq!(parser.restore_state(&state[..state.len() - 1]), Err(StateError::Malformed));
+  /* the escape holds only one hex digit */
+  let mut bad = state.clone();
+  bad[escape + 1] = 0x10;
+  assert_eq!(parser.restore_state(&bad), Err(StateError::Invalid));
+  /* the bit of the root container is always set */
+  let mut bad = state.clone();
+  bad[stack] = 0;
+  assert_eq!(parser.restore_state(&bad), Err(StateError::Invalid));
+  assert_eq!(parser.restore_state(&state), Ok(()));
+  ```
+  */
+  pub fn restore_state(&mut self, state: &[u8]) -> Result<(), StateError> {
+    let mut reader = StateReader { data: state };
+    if reader.bytes(STATE_MAGIC.len())? != STATE_MAGIC {
+      return Err(StateError::Malformed);
+    }
+    let version = reader.u8()?;
+    if version != STATE_VERSION {
+      return Err(StateError::UnsupportedVersion(version));
+    }
+    let option = ParserOption::from_bits(reader.u32()?).ok_or(StateError::Invalid)?;
+    let mut parser = StreamParser::new(option);
+    parser.raw.position = reader.usize()?;
+    parser.raw.line = reader.usize()?;
+    parser.raw.column = reader.usize()?;
+    parser.byte_position = reader.usize()?;
+    parser.astral = reader.usize()?;
+    parser.astral_line = reader.usize()?;
+    let state;
+    [parser.raw.location, state, parser.raw.flag, parser.raw.substate] = reader.array()?;
+    parser.raw.state = decode_state(state).ok_or(StateError::Invalid)?;
+    parser.raw.escape = reader.u16()?;
+    parser.raw.set_prev_pair(reader.u16()?);
+    parser.utf8.code = reader.u32()?;
+    parser.utf8.rest = reader.u16()?;
+    parser.utf8.total = reader.u16()?;
+    let len = reader.usize()?;
+    parser.raw.len = StackLength::try_from(len).map_err(|_| StateError::Invalid)?;
+    let stack = reader.bytes(len.div_ceil(8))?;
+    let counters = match reader.u8()? {
+      0 => None,
+      1 => {
+        let (length, nodes, count) = (reader.usize()?, reader.usize()?, reader.usize()?);
+        if count != len {
+          return Err(StateError::Invalid);
+        }
+        let counts = (0..count).map(|_| reader.usize()).collect::<Result<Vec<_>, _>>()?;
+        Some((length, nodes, counts))
+      }
+      _ => return Err(StateError::Malformed),
+    };
+    let path = match reader.u8()? {
+      0 => None,
+      1 => Some(Arc::new(read_path(&mut reader, len)?)),
+      _ => return Err(StateError::Malformed),
+    };
+    let ijson = match reader.u8()? {
+      0 => None,
+      1 => Some(Arc::new(read_ijson(&mut reader, len)?)),
+      _ => return Err(StateError::Malformed),
+    };
+    if !reader.data.is_empty() {
+      return Err(StateError::Malformed);
+    }
+    if !parser.is_valid_state() || !is_valid_stack(stack, len) {
+      return Err(StateError::Invalid);
+    }
+    #[cfg(feature = "fixed-stack")]
+    if stack.len() > FIXED_STACK {
+      return Err(StateError::Invalid);
+    }
+
+    parser.reserve_stack(stack.len());
+    parser.raw.get_stack_mut(stack.len()).copy_from_slice(stack);
+    if let Some(mut limiter) = self.limits.take() {
+      let (length, nodes, counts) = counters.unwrap_or_else(|| (0, 0, vec![0; len]));
+      limiter.set_counters(length, nodes, counts);
+      parser.limits = Some(limiter);
+    }
+    if self.path.is_some() {
+      parser.path = Some(path.unwrap_or_default());
+    }
+    if self.ijson.is_some() {
+      parser.ijson = Some(ijson.unwrap_or_else(|| Arc::new(IJsonChecker::with_depth(len))));
+    }
+    *self = parser;
+    Ok(())
+  }
+
+  /** Check the fields (except the stack) restored by `restore_state` */
+  fn is_valid_state(&self) -> bool {
+    let raw = &self.raw;
+    let (location, state) = (raw.location, raw.state);
+    let is_comment = (VAL_COMMENT_MAY_START..=VAL_MULTI_LINE_COMMENT_MAY_END).contains(&state);
+    let location_valid = match location {
+      LOC_ROOT_START | LOC_ROOT_END => raw.len == 0,
+      LOC_EOF => raw.len == 0 && state == VAL_EMPTY,
+      LOC_KEY_FIRST_START..=LOC_ELEMENT_START | LOC_KEY_END..=LOC_ELEMENT_END => raw.len != 0,
+      _ => false,
+    };
+    let state_valid = state <= VAL_LAST
+      && (location < LOC_ROOT_END || state == VAL_EMPTY || is_comment)
+      && match state {
+        VAL_NUMBER => matches!(raw.substate, 0 | 1 | 0xFF),
+        state => raw.substate <= max_substate(state),
+      };
+    /* each hex digit shifts `escape` by 4 bits */
+    let escape_valid = escape_digits(state, raw.substate)
+      .is_none_or(|digits| raw.escape as u32 >> (4 * digits.min(4) as u32) == 0);
+    #[cfg(not(feature = "no-combine-surrogate"))]
+    let escape_valid = escape_valid
+      && (!matches!(state, VAL_STRING_UNICODE_NEXT | VAL_IDENTIFIER_ESCAPE_NEXT)
+        || (0xD800..=0xDBFF).contains(&raw.get_prev_pair())
+          && (raw.substate >= 2 || raw.escape == raw.get_prev_pair()));
+    /* `total` is the number of continuation bytes */
+    let utf8_valid =
+      self.utf8.rest == 0 || self.utf8.rest <= self.utf8.total && self.utf8.total <= 3;
+    let position_valid = raw.line <= raw.position
+      && raw.column <= raw.position
+      && raw.position <= self.byte_position
+      && self.astral_line <= raw.line
+      && (self.astral_line != raw.line || self.astral <= raw.column);
+    location_valid
+      && state_valid
+      && escape_valid
+      && raw.flag & !(FLAG_MEET_CR | FLAG_SINGLE_QUOTE | FLAG_PYTHON_NONE | FLAG_TRAILING_TEXT) == 0
+      && utf8_valid
+      && position_valid
+  }
+}
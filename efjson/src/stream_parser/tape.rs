@@ -0,0 +1,398 @@
+use super::{Category, JsonPointer, PathSegment, StreamError, StreamParser, Token, TokenInfo};
+use crate::ParserOption;
+use std::borrow::Cow;
+
+/** The kind of a value in a `Tape` */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TapeKind {
+  Null,
+  Boolean,
+  Number,
+  /** a string, or a key (which may be an identifier with `ParserOption::IDENTIFIER_KEY`) */
+  String,
+  Array,
+  Object,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct TapeEntry {
+  kind: TapeKind,
+  /** the span of the value in bytes */
+  start: usize,
+  end: usize,
+  /** the index of the entry after the value and its children */
+  next: usize,
+  /** the number of elements or members */
+  len: usize,
+}
+
+/**
+A structural index of a document, built in a single pass of `StreamParser`.
+
+Each value (and each key) is an entry storing its span in the source, in the order of the input.
+Arrays and objects store where they end, so that they are skipped in O(1),
+and the members of an object are stored as the key followed by the value.
+Values are not decoded (except keys with escapes, which are decoded once),
+see `TapeValue::deserialize` to decode a value on demand.
+
+# Example
+```rust
+use efjson::{stream_parser::{Tape, TapeKind}, ParserOption};
+let source = r#"{"servers": [{"host": "a", "port": 80}, {"host": "b", "port": 8080}], "debug": true}"#;
+let tape = Tape::parse(ParserOption::empty(), source).unwrap();
+let root = tape.get_root().unwrap();
+assert_eq!(root.get_kind(), TapeKind::Object);
+assert_eq!(root.len(), 2);
+assert_eq!(root.get("debug").unwrap().get_text(), "true");
+
+let port = tape.pointer("/servers/1/port").unwrap();
+assert_eq!(port.get_text(), "8080");
+let hosts: Vec<_> = tape.pointer("/servers").unwrap().iter().map(|server| server.get("host").unwrap().get_text()).collect();
+assert_eq!(hosts, ["\"a\"", "\"b\""]);
+```
+*/
+#[derive(Debug, Clone)]
+pub struct Tape<'a> {
+  source: &'a str,
+  option: ParserOption,
+  entries: Vec<TapeEntry>,
+  /** the keys with escapes decoded, by the index of their entries */
+  keys: Vec<(usize, String)>,
+}
+
+/** The state of building a `Tape` */
+struct TapeBuilder {
+  entries: Vec<TapeEntry>,
+  keys: Vec<(usize, String)>,
+  /** the containers, and the number of entries directly in them */
+  stack: Vec<(usize, usize)>,
+  /** the number or the identifier being parsed, which ends at the next token of another category */
+  open: Option<usize>,
+  /** the decoded key being parsed, and whether it has escapes */
+  key: Option<(String, bool)>,
+}
+impl TapeBuilder {
+  /** Push a value starting at `start`, and return whether it is a key */
+  fn push(&mut self, kind: TapeKind, start: usize) -> bool {
+    let index = self.entries.len();
+    let mut is_key = false;
+    if let Some((parent, count)) = self.stack.last_mut() {
+      let parent = &mut self.entries[*parent];
+      is_key = parent.kind == TapeKind::Object && *count % 2 == 0;
+      if is_key || parent.kind == TapeKind::Array {
+        parent.len += 1;
+      }
+      *count += 1;
+    }
+    self.entries.push(TapeEntry { kind, start, end: start, next: index + 1, len: 0 });
+    is_key
+  }
+  fn push_key(&mut self, c: char, escaped: bool) {
+    if let Some((key, has_escape)) = &mut self.key {
+      key.push(c);
+      *has_escape |= escaped;
+    }
+  }
+  fn end_key(&mut self, index: usize) {
+    if let Some((key, true)) = self.key.take() {
+      self.keys.push((index, key));
+    }
+  }
+  fn feed(&mut self, offset: usize, token: Token) {
+    let category = token.info.get_category();
+    if let Some(index) = self.open {
+      let entry = &mut self.entries[index];
+      let same = match entry.kind {
+        TapeKind::Number => category == Category::Number,
+        _ => category == Category::Identifier,
+      };
+      if !same {
+        entry.end = offset;
+        self.open = None;
+        self.end_key(index);
+      }
+    }
+    let end = offset + token.c.len_utf8();
+    match token.info {
+      TokenInfo::ObjectStart | TokenInfo::ArrayStart => {
+        let kind =
+          if token.info == TokenInfo::ObjectStart { TapeKind::Object } else { TapeKind::Array };
+        self.push(kind, offset);
+        self.stack.push((self.entries.len() - 1, 0));
+      }
+      TokenInfo::ObjectEnd | TokenInfo::ArrayEnd => {
+        if let Some((index, _)) = self.stack.pop() {
+          let next = self.entries.len();
+          let entry = &mut self.entries[index];
+          (entry.end, entry.next) = (end, next);
+        }
+      }
+      TokenInfo::StringStart => {
+        self.key = self.push(TapeKind::String, offset).then(|| (String::new(), false));
+      }
+      TokenInfo::StringEnd => {
+        let index = self.entries.len() - 1;
+        self.entries[index].end = end;
+        self.end_key(index);
+      }
+      TokenInfo::StringNormal => self.push_key(token.c, false),
+      TokenInfo::IdentifierNormal if self.open.is_some() => self.push_key(token.c, false),
+      TokenInfo::IdentifierNormal | TokenInfo::IdentifierEscapeStart(0, _)
+        if self.open.is_none() =>
+      {
+        self.push(TapeKind::String, offset);
+        self.open = Some(self.entries.len() - 1);
+        self.key = Some((String::new(), false));
+        if token.info == TokenInfo::IdentifierNormal {
+          self.push_key(token.c, false);
+        }
+      }
+      TokenInfo::StringEscape(c)
+      | TokenInfo::StringEscapeUnicode(_, Some(c))
+      | TokenInfo::StringEscapeHex(_, Some(c))
+      | TokenInfo::IdentifierEscape(_, Some(c)) => self.push_key(c, true),
+      /* a lone surrogate is replaced with U+FFFD */
+      TokenInfo::StringEscapeSurrogate(_) => self.push_key(char::REPLACEMENT_CHARACTER, true),
+      TokenInfo::Null(0, done) | TokenInfo::True(0, done) | TokenInfo::False(0, done) => {
+        let kind = if category == Category::Null { TapeKind::Null } else { TapeKind::Boolean };
+        self.push(kind, offset);
+        if done {
+          let index = self.entries.len() - 1;
+          self.entries[index].end = end;
+        }
+      }
+      TokenInfo::Null(_, true) | TokenInfo::True(_, true) | TokenInfo::False(_, true) => {
+        let index = self.entries.len() - 1;
+        self.entries[index].end = end;
+      }
+      _ if category == Category::Number && self.open.is_none() => {
+        self.push(TapeKind::Number, offset);
+        self.open = Some(self.entries.len() - 1);
+      }
+      _ => {}
+    }
+  }
+}
+
+impl<'a> Tape<'a> {
+  /**
+  Parse a whole document into a tape.
+
+  # Errors
+  The error of `StreamParser`, including the end of input.
+  */
+  pub fn parse(option: ParserOption, source: &'a str) -> Result<Self, StreamError> {
+    let mut parser = StreamParser::new(option);
+    let mut builder = TapeBuilder {
+      entries: Vec::new(),
+      keys: Vec::new(),
+      stack: Vec::new(),
+      open: None,
+      key: None,
+    };
+    for (offset, c) in source.char_indices().chain([(source.len(), '\0')]) {
+      let (comma, token) = parser.feed_pair(c)?;
+      for token in comma.into_iter().chain([token]) {
+        builder.feed(offset, token);
+      }
+    }
+    Ok(Self { source, option, entries: builder.entries, keys: builder.keys })
+  }
+  /** Get the source of the document */
+  pub fn get_source(&self) -> &'a str {
+    self.source
+  }
+  /** Get the option the document is parsed with */
+  pub fn get_option(&self) -> ParserOption {
+    self.option
+  }
+  /** Get the number of entries (values and keys) */
+  pub fn len(&self) -> usize {
+    self.entries.len()
+  }
+  /** Whether the tape has no entries, which happens only for an empty document (see `get_root`) */
+  pub fn is_empty(&self) -> bool {
+    self.entries.is_empty()
+  }
+  /**
+  Get the root value, `None` if the document is empty (accepted with `ParserOption::ALLOW_EMPTY_VALUE`).
+
+  # Example
+  ```rust
+  use efjson::{stream_parser::Tape, ParserOption};
+  let tape = Tape::parse(ParserOption::ALLOW_EMPTY_VALUE, "  ").unwrap();
+  assert!(tape.is_empty());
+  assert!(tape.get_root().is_none());
+  assert!(tape.pointer("").is_none());
+  assert!(tape.pointer("/a").is_none());
+  ```
+  */
+  pub fn get_root(&self) -> Option<TapeValue<'_, 'a>> {
+    (!self.entries.is_empty()).then_some(TapeValue { tape: self, index: 0 })
+  }
+  /**
+  Get the value referred by a JSON Pointer (RFC 6901), such as `/servers/3/port`.
+
+  Returns `None` if the value does not exist or the pointer is invalid.
+  */
+  pub fn pointer(&self, pointer: &str) -> Option<TapeValue<'_, 'a>> {
+    let mut value = self.get_root()?;
+    if pointer.is_empty() {
+      return Some(value);
+    }
+    for segment in pointer.strip_prefix('/')?.split('/') {
+      value = match value.get_kind() {
+        TapeKind::Object if segment.contains('~') => {
+          value.get(&segment.replace("~1", "/").replace("~0", "~"))?
+        }
+        TapeKind::Object => value.get(segment)?,
+        TapeKind::Array => value.get_index(parse_index(segment)?)?,
+        _ => return None,
+      };
+    }
+    Some(value)
+  }
+  /** Get the value at the path, see `StreamParser::current_path` */
+  pub fn get_path(&self, path: &JsonPointer) -> Option<TapeValue<'_, 'a>> {
+    let mut value = self.get_root()?;
+    for segment in path.get_segments() {
+      value = match segment {
+        PathSegment::Key(key) => value.get(key)?,
+        PathSegment::Index(index) => value.get_index(*index)?,
+      };
+    }
+    Some(value)
+  }
+}
+
+/** Parse an index of JSON Pointer, which has no leading zeros */
+fn parse_index(segment: &str) -> Option<usize> {
+  if segment.is_empty()
+    || (segment.len() > 1 && segment.starts_with('0'))
+    || !segment.bytes().all(|b| b.is_ascii_digit())
+  {
+    return None;
+  }
+  segment.parse().ok()
+}
+
+/** A value in a `Tape` */
+#[derive(Debug, Clone, Copy)]
+pub struct TapeValue<'t, 'a> {
+  tape: &'t Tape<'a>,
+  index: usize,
+}
+impl<'t, 'a> TapeValue<'t, 'a> {
+  fn entry(&self) -> &'t TapeEntry {
+    &self.tape.entries[self.index]
+  }
+  fn at(&self, index: usize) -> TapeValue<'t, 'a> {
+    TapeValue { tape: self.tape, index }
+  }
+  pub fn get_kind(&self) -> TapeKind {
+    self.entry().kind
+  }
+  /** Get the span of the value in the source (in bytes) */
+  pub fn get_span(&self) -> std::ops::Range<usize> {
+    self.entry().start..self.entry().end
+  }
+  /** Get the text of the value in the source, without decoding it */
+  pub fn get_text(&self) -> &'a str {
+    &self.tape.source[self.get_span()]
+  }
+  /** Get the number of elements of an array or members of an object, `0` for others */
+  pub fn len(&self) -> usize {
+    self.entry().len
+  }
+  /** Whether the value has no elements or members */
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+  /** Iterate over the elements of an array (or the values of an object), skipping each one in O(1) */
+  pub fn iter(&self) -> TapeIter<'t, 'a> {
+    let keyed = self.get_kind() == TapeKind::Object;
+    TapeIter { value: self.at(self.index + 1), end: self.entry().next, keyed }
+  }
+  /** Iterate over the members of an object, as the decoded keys and the values */
+  pub fn members(&self) -> impl Iterator<Item = (Cow<'t, str>, TapeValue<'t, 'a>)> {
+    let end = if self.get_kind() == TapeKind::Object { self.entry().next } else { self.index + 1 };
+    let mut key = self.at(self.index + 1);
+    std::iter::from_fn(move || {
+      if key.index >= end {
+        return None;
+      }
+      let value = key.at(key.entry().next);
+      let member = (key.decode_key(), value);
+      key = value.at(value.entry().next);
+      Some(member)
+    })
+  }
+  /** Get the element of an array at `index` */
+  pub fn get_index(&self, index: usize) -> Option<TapeValue<'t, 'a>> {
+    if self.get_kind() != TapeKind::Array {
+      return None;
+    }
+    self.iter().nth(index)
+  }
+  /** Get the value of the member of an object, the last one if the key is repeated */
+  pub fn get(&self, key: &str) -> Option<TapeValue<'t, 'a>> {
+    if self.get_kind() != TapeKind::Object {
+      return None;
+    }
+    self.members().filter(|(k, _)| k == key).last().map(|(_, value)| value)
+  }
+  /** Decode a key, which is borrowed from the source if it has no escapes */
+  fn decode_key(&self) -> Cow<'t, str> {
+    let keys = &self.tape.keys;
+    if let Ok(i) = keys.binary_search_by_key(&self.index, |(index, _)| *index) {
+      return Cow::Borrowed(&keys[i].1);
+    }
+    let text = self.get_text();
+    match text.chars().next() {
+      Some('"' | '\'') => Cow::Borrowed(&text[1..text.len() - 1]),
+      _ => Cow::Borrowed(text),
+    }
+  }
+
+  /**
+  Decode the value with the existing deserializers, see `deserialize::deserialize`.
+
+  # Example
+  ```rust
+  use efjson::{stream_parser::Tape, ParserOption};
+  let tape = Tape::parse(ParserOption::empty(), r#"{"a": [1, 2, 3], "b": "x\ny"}"#).unwrap();
+  let a: Vec<i32> = tape.pointer("/a").unwrap().deserialize().unwrap();
+  assert_eq!(a, [1, 2, 3]);
+  assert_eq!(tape.pointer("/b").unwrap().deserialize::<String>().unwrap(), "x\ny");
+  ```
+  */
+  #[cfg(feature = "deserialize")]
+  pub fn deserialize<T: crate::deserialize::DefaultDeserializable<T>>(
+    &self,
+  ) -> Result<T, crate::deserialize::DeserError> {
+    crate::deserialize::deserialize(self.tape.option, self.get_text())
+  }
+}
+
+/** The iterator of `TapeValue::iter` */
+#[derive(Debug, Clone)]
+pub struct TapeIter<'t, 'a> {
+  value: TapeValue<'t, 'a>,
+  end: usize,
+  /** whether each value is after a key */
+  keyed: bool,
+}
+impl<'t, 'a> Iterator for TapeIter<'t, 'a> {
+  type Item = TapeValue<'t, 'a>;
+  fn next(&mut self) -> Option<Self::Item> {
+    if self.keyed && self.value.index < self.end {
+      self.value = self.value.at(self.value.entry().next);
+    }
+    if self.value.index >= self.end {
+      return None;
+    }
+    let value = self.value;
+    self.value = value.at(value.entry().next);
+    Some(value)
+  }
+}
@@ -0,0 +1,124 @@
+use super::outer::*;
+use super::{is_next_line, StreamError, StreamParser};
+use crate::ParserOption;
+
+impl StreamParser {
+  /**
+  Get the length (in bytes) of the run at the start of `s` which only moves the position,
+  that is, string content or whitespace, which can be skipped without the C parser.
+  */
+  pub(super) fn get_run_length(&self, s: &str) -> usize {
+    let raw = &self.raw;
+    /* the position must not reach its maximum, where the C parser reports an overflow */
+    if raw.flag & FLAG_MEET_CR != 0
+      || raw.position.checked_add(s.len()).is_none_or(|end| end == Position::MAX)
+    {
+      return 0;
+    }
+    let end = match raw.state {
+      VAL_STRING => {
+        let quote = if raw.flag & FLAG_SINGLE_QUOTE != 0 { '\'' } else { '"' };
+        s.find(|c: char| c == quote || c == '\\' || c <= '\x1F' || c == '\x7F' || is_next_line(c))
+      }
+      VAL_EMPTY if raw.flag & FLAG_TRAILING_TEXT != 0 => s.find(|c| c == '\0' || is_next_line(c)),
+      VAL_EMPTY if raw.location != LOC_EOF => s.find(|c| c != ' ' && c != '\t'),
+      _ => Some(0),
+    };
+    end.unwrap_or(s.len())
+  }
+  /** Whether each character must be fed with `feed_pair`, since its token is checked, tracked or repaired */
+  pub(super) fn needs_tokens(&self) -> bool {
+    self.utf8.rest != 0
+      || self.limits.is_some()
+      || self.path.is_some()
+      || self.ijson.is_some()
+      || self.is_repairing()
+  }
+  /** Skip a run got by `get_run_length`, which contains no line breaks */
+  pub(super) fn skip_run(&mut self, run: &str) {
+    let count = run.chars().count();
+    let astral = run.chars().filter(|&c| c > '\u{FFFF}').count();
+    self.raw.position += count;
+    self.raw.column += count;
+    self.byte_position += run.len();
+    if astral != 0 {
+      /* the supplementary characters before on the current line have been counted */
+      if self.astral_line != self.raw.line {
+        (self.astral, self.astral_line) = (0, self.raw.line);
+      }
+      self.astral += astral;
+    }
+  }
+
+  /**
+  Feed a string to the parser without producing tokens, for validation.
+
+  Runs of string content and whitespace are skipped in bulk,
+  and the other characters are fed to the C parser without building `Token`s.
+  It falls back to feeding characters one by one with `Limits`, path tracking, I-JSON,
+  the options of `ParserOption::make_repair`, or an incomplete UTF-8 sequence fed by `feed_byte`.
+
+  # Note
+  If the string is ended, you need to explicitly pass `'\0'` to notify the parser.
+
+  # Errors
+  The same as `feed_into`: the state machine stops at the character that caused the error,
+  so the position of the error is exact.
+
+  # Example
+  ```rust
+  use efjson::{stream_parser::{ErrorKind, StreamParser}, ParserOption};
+  let mut parser = StreamParser::new(ParserOption::empty());
+  parser.skip("{\"text\": \"a long string\",    ").unwrap();
+  assert_eq!(parser.get_position(), 29);
+  let err = parser.skip("\"b\": \"x\ty\"}").unwrap_err();
+  assert_eq!((err.kind, err.position), (ErrorKind::ControlCharacterForbiddenInString, 36));
+  ```
+  */
+  pub fn skip(&mut self, s: &str) -> Result<(), StreamError> {
+    if self.needs_tokens() {
+      for c in s.chars() {
+        self.feed_pair(c)?;
+      }
+      return Ok(());
+    }
+    let mut rest = s;
+    while let Some(c) = rest.chars().next() {
+      let run = self.get_run_length(rest);
+      if run != 0 {
+        self.skip_run(&rest[..run]);
+        rest = &rest[run..];
+        continue;
+      }
+      /* a character after a lone high surrogate is fed again */
+      if self.feed_ctoken(c, c.len_utf8())?.is_consumed() {
+        rest = &rest[c.len_utf8()..];
+      }
+    }
+    Ok(())
+  }
+}
+
+/**
+Check whether the string is a valid JSON document under the option.
+
+It is faster than parsing into tokens, see `StreamParser::skip`.
+
+# Errors
+The first error found, with its exact position.
+
+# Example
+```rust
+use efjson::{stream_parser::ErrorKind, validate, ParserOption};
+assert!(validate(ParserOption::empty(), "{\"a\": [1, 2.5, \"x\"]}").is_ok());
+assert!(validate(ParserOption::make_json5(), "{a: [1, 2,], /* ok */}").is_ok());
+let err = validate(ParserOption::empty(), "[\"a\", \"b\tc\"]").unwrap_err();
+assert_eq!((err.kind, err.position), (ErrorKind::ControlCharacterForbiddenInString, 8));
+assert_eq!(validate(ParserOption::empty(), "[1").unwrap_err().kind, ErrorKind::Eof);
+```
+*/
+pub fn validate(option: ParserOption, s: &str) -> Result<(), StreamError> {
+  let mut parser = StreamParser::new(option);
+  parser.skip(s)?;
+  parser.skip("\0")
+}
@@ -0,0 +1,50 @@
+/*!
+The classification of characters used by the parser, for writers to agree with it.
+
+They are backed by the tables of the C parser,
+so they follow the feature `ascii-only` (only ASCII characters are classified then).
+*/
+
+mod outer {
+  unsafe extern "C" {
+    pub fn efjson_isWhitespace(u: u32, fit_json5: std::ffi::c_int) -> std::ffi::c_int;
+    pub fn efjson_isIdentifierStart(u: u32) -> std::ffi::c_int;
+    pub fn efjson_isIdentifierNext(u: u32) -> std::ffi::c_int;
+  }
+}
+use outer::*;
+
+/** Whether the character is whitespace in JSON (space, tab, line feed and carriage return) */
+pub fn is_json_whitespace(c: char) -> bool {
+  unsafe { efjson_isWhitespace(c as u32, 0) != 0 }
+}
+/** Whether the character is whitespace with `ParserOption::JSON5_WHITESPACE` */
+pub fn is_json5_whitespace(c: char) -> bool {
+  unsafe { efjson_isWhitespace(c as u32, 1) != 0 }
+}
+/** Whether the character can start an identifier key (`ParserOption::IDENTIFIER_KEY`) */
+pub fn is_identifier_start(c: char) -> bool {
+  unsafe { efjson_isIdentifierStart(c as u32) != 0 }
+}
+/** Whether the character can follow the first character of an identifier key */
+pub fn is_identifier_part(c: char) -> bool {
+  unsafe { efjson_isIdentifierNext(c as u32) != 0 }
+}
+/**
+Whether the string can be written as an identifier key without escapes,
+that is, the parser accepts it unquoted with `ParserOption::IDENTIFIER_KEY`.
+
+# Example
+```rust
+use efjson::unicode::is_valid_identifier;
+assert!(is_valid_identifier("$key_1"));
+assert!(is_valid_identifier("null"));
+assert!(!is_valid_identifier("1st"));
+assert!(!is_valid_identifier("a-b"));
+assert!(!is_valid_identifier(""));
+```
+*/
+pub fn is_valid_identifier(s: &str) -> bool {
+  let mut chars = s.chars();
+  chars.next().is_some_and(is_identifier_start) && chars.all(is_identifier_part)
+}
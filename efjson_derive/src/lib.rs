@@ -73,37 +73,42 @@ pub fn derive_answer_fn(input: proc_macro::TokenStream) -> proc_macro::TokenStre
       start_content.append(proc_macro2::Punct::new(',', proc_macro2::Spacing::Alone));
     }
 
-    let mut feed_content = quote_spanned! {span=>};
-    for (idx, field) in fields.iter().enumerate() {
-      let ident = &field.ident;
-      let typ = &field.ty;
-      feed_content.extend(quote_spanned! {span=>
-        #idx => match ::efjson::deserialize::Deserializer::feed_token(
-          unsafe {
-            &mut self.subreceiver.assume_init_mut().#ident
-              as &mut <#typ as ::efjson::deserialize::DefaultDeserializable<#typ>>::DefaultDeserializer
-          },
-          token,
-        )? {
-          ::efjson::deserialize::DeserResult::Complete(r) => {
-            unsafe { ::std::ptr::write(&mut (*self.target.as_mut_ptr()).#ident, r) };
-            self.flag[#idx] = true;
-            unsafe { ::std::mem::ManuallyDrop::drop(&mut self.subreceiver.assume_init_mut().#ident) };
-            self.index = usize::max_value();
-            ::efjson::deserialize::DeserResult::Complete(())
-          }
-          ::efjson::deserialize::DeserResult::CompleteWithRollback(r) => {
-            unsafe { ::std::ptr::write(&mut (*self.target.as_mut_ptr()).#ident, r) };
-            self.flag[#idx] = true;
-            unsafe { ::std::mem::ManuallyDrop::drop(&mut self.subreceiver.assume_init_mut().#ident) };
-            self.index = usize::max_value();
-            ::efjson::deserialize::DeserResult::CompleteWithRollback(())
+    let make_feed_content = |method: proc_macro2::Ident| {
+      let mut feed_content = quote_spanned! {span=>};
+      for (idx, field) in fields.iter().enumerate() {
+        let ident = &field.ident;
+        let typ = &field.ty;
+        feed_content.extend(quote_spanned! {span=>
+          #idx => match ::efjson::deserialize::Deserializer::#method(
+            unsafe {
+              &mut self.subreceiver.assume_init_mut().#ident
+                as &mut <#typ as ::efjson::deserialize::DefaultDeserializable<#typ>>::DefaultDeserializer
+            },
+            token,
+          )? {
+            ::efjson::deserialize::DeserResult::Complete(r) => {
+              unsafe { ::std::ptr::write(&mut (*self.target.as_mut_ptr()).#ident, r) };
+              self.flag[#idx] = true;
+              unsafe { ::std::mem::ManuallyDrop::drop(&mut self.subreceiver.assume_init_mut().#ident) };
+              self.index = usize::max_value();
+              ::efjson::deserialize::DeserResult::Complete(())
+            }
+            ::efjson::deserialize::DeserResult::CompleteWithRollback(r) => {
+              unsafe { ::std::ptr::write(&mut (*self.target.as_mut_ptr()).#ident, r) };
+              self.flag[#idx] = true;
+              unsafe { ::std::mem::ManuallyDrop::drop(&mut self.subreceiver.assume_init_mut().#ident) };
+              self.index = usize::max_value();
+              ::efjson::deserialize::DeserResult::CompleteWithRollback(())
+            }
+            ::efjson::deserialize::DeserResult::Continue => ::efjson::deserialize::DeserResult::Continue
           }
-          ::efjson::deserialize::DeserResult::Continue => ::efjson::deserialize::DeserResult::Continue
-        }
-      });
-      feed_content.append(proc_macro2::Punct::new(',', proc_macro2::Spacing::Alone));
-    }
+        });
+        feed_content.append(proc_macro2::Punct::new(',', proc_macro2::Spacing::Alone));
+      }
+      feed_content
+    };
+    let feed_content = make_feed_content(proc_macro2::Ident::new("feed_token", span));
+    let feed_coarse_content = make_feed_content(proc_macro2::Ident::new("feed_coarse", span));
 
     let mut end_content = quote_spanned! {span=>};
     for (idx, field) in fields.iter().enumerate() {
@@ -130,6 +135,15 @@ pub fn derive_answer_fn(input: proc_macro::TokenStream) -> proc_macro::TokenStre
             _ => unsafe { ::std::hint::unreachable_unchecked() },
           })
         }
+        fn feed_value_coarse(
+          &mut self,
+          token: ::efjson::stream_parser::CoarseToken,
+        ) -> ::std::result::Result<::efjson::deserialize::DeserResult<()>, ::efjson::deserialize::DeserError> {
+          Ok(match self.index {
+            #feed_coarse_content
+            _ => unsafe { ::std::hint::unreachable_unchecked() },
+          })
+        }
         fn end(&mut self) -> ::std::result::Result<#ident, ::efjson::deserialize::DeserError> {
           if self.flag.iter().all(|f| *f) {
             self.flag.fill(false);